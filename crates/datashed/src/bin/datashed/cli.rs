@@ -27,8 +27,22 @@ pub(crate) struct Args {
 
 #[derive(Debug, Subcommand)]
 pub(crate) enum Command {
+    Archive(Archive),
+    Config(Config),
+    Dedup(Dedup),
+    Dehyphenate(Dehyphenate),
+    Export(Export),
     Index(Index),
     Init(Init),
+    Log(Log),
+    Normalize(Normalize),
+    Restore(Restore),
+    Select(Select),
+    Split(Split),
+    Stats(Stats),
+    StripBoilerplate(StripBoilerplate),
+    Subjects(Subjects),
+    Verify(Verify),
     Version(Version),
 }
 
@@ -0,0 +1,151 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use indicatif::ParallelProgressIterator;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::prelude::*;
+
+/// The name of the manifest, which lists the SHA-256 checksum of each
+/// file of an archive (in the format of `sha256sum`).
+pub(crate) const MANIFEST: &str = "MANIFEST.sha256";
+
+const PBAR_HASH: &str = "Hashing files: {human_pos} ({percent}%) | \
+        elapsed: {elapsed_precise}{msg}";
+
+const PBAR_ARCHIVE: &str = "Archiving files: {human_pos} ({percent}%) | \
+        elapsed: {elapsed_precise}{msg}";
+
+/// Pack the datashed into a single versioned archive
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Archive {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// The compression level of the zstd encoder (1-22).
+    #[arg(short, long, default_value = "3")]
+    level: i32,
+
+    /// Whether to overwrite an existing archive or not.
+    #[arg(short, long)]
+    force: bool,
+
+    /// Write the archive into `filename` instead of
+    /// `<name>-<version>.tar.zst` in the current directory.
+    #[arg(short, long, value_name = "filename")]
+    output: Option<PathBuf>,
+}
+
+/// Returns the SHA-256 checksum of a file.
+pub(crate) fn checksum(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+impl Archive {
+    pub(crate) fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let base_dir = datashed.base_dir();
+        let config = datashed.config()?;
+        let metadata = &config.metadata;
+
+        if metadata.name.is_empty() {
+            bail!("the datashed must have a name to be archived");
+        }
+
+        let prefix = format!("{}-{}", metadata.name, metadata.version);
+        let output = self.output.unwrap_or_else(|| {
+            PathBuf::from(format!("{prefix}.tar.zst"))
+        });
+
+        if output.exists() && !self.force {
+            bail!("archive '{}' already exists", output.display());
+        }
+
+        let mut files = vec![PathBuf::from(Datashed::CONFIG)];
+        for name in
+            [Datashed::CHANGELOG, Datashed::INDEX, Datashed::SUBJECTS]
+        {
+            if base_dir.join(name).is_file() {
+                files.push(name.into());
+            }
+        }
+
+        let mut documents: Vec<PathBuf> =
+            WalkDir::new(datashed.data_dir())
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|dirent| dirent.file_type().is_file())
+                .filter_map(|dirent| {
+                    dirent
+                        .path()
+                        .strip_prefix(base_dir)
+                        .ok()
+                        .map(Path::to_path_buf)
+                })
+                .collect();
+
+        documents.sort();
+        files.extend(documents);
+
+        let pbar =
+            ProgressBarBuilder::new(PBAR_HASH, self.common.quiet)
+                .len(files.len() as u64)
+                .build();
+
+        let checksums = files
+            .par_iter()
+            .progress_with(pbar)
+            .map(|path| checksum(&base_dir.join(path)))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut manifest = String::new();
+        for (path, checksum) in files.iter().zip(checksums.iter()) {
+            manifest
+                .push_str(&format!("{checksum}  {}\n", path.display()));
+        }
+
+        let writer = BufWriter::new(File::create(&output)?);
+        let encoder = zstd::Encoder::new(writer, self.level)?;
+        let mut builder = tar::Builder::new(encoder);
+        let prefix = Path::new(&prefix);
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        builder.append_data(
+            &mut header,
+            prefix.join(MANIFEST),
+            manifest.as_bytes(),
+        )?;
+
+        let pbar =
+            ProgressBarBuilder::new(PBAR_ARCHIVE, self.common.quiet)
+                .len(files.len() as u64)
+                .build();
+
+        for path in files.iter().progress_with(pbar) {
+            builder.append_path_with_name(
+                base_dir.join(path),
+                prefix.join(path),
+            )?;
+        }
+
+        let mut writer = builder.into_inner()?.finish()?;
+        writer.flush()?;
+
+        if self.common.verbose {
+            eprintln!(
+                "Archived {} files into {}",
+                files.len(),
+                output.display()
+            );
+        }
+
+        Ok(SUCCESS)
+    }
+}
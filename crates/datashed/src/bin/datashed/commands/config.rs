@@ -0,0 +1,223 @@
+use std::fs;
+
+use clap::Subcommand;
+use datashed::Config as DatashedConfig;
+use toml_edit::{DocumentMut, Item, Table, TableLike, Value};
+
+use crate::prelude::*;
+
+/// Get and set options of the datashed config
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Config {
+    #[command(subcommand)]
+    cmd: ConfigCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum ConfigCommand {
+    /// Print the value of a key
+    Get {
+        #[command(flatten)]
+        common: CommonArgs,
+
+        /// The dotted path of the key (e.g. `metadata.name`).
+        key: String,
+    },
+
+    /// Set the value of a key
+    Set {
+        #[command(flatten)]
+        common: CommonArgs,
+
+        /// Always store the value as a string. Otherwise, the value is
+        /// parsed as a TOML value (e.g. a number or an array), unless
+        /// the key already holds a string.
+        #[arg(short, long)]
+        string: bool,
+
+        /// The dotted path of the key (e.g. `metadata.name`).
+        key: String,
+
+        /// The new value of the key.
+        value: String,
+    },
+
+    /// Remove a key
+    Unset {
+        #[command(flatten)]
+        common: CommonArgs,
+
+        /// The dotted path of the key (e.g. `metadata.description`).
+        key: String,
+    },
+
+    /// List all keys and their values
+    List {
+        #[command(flatten)]
+        common: CommonArgs,
+    },
+}
+
+/// Returns the representation of a value without surrounding
+/// whitespace and comments. Strings are returned without quotes.
+fn format_value(value: &Value) -> String {
+    match value.as_str() {
+        Some(s) => s.to_string(),
+        None => value.clone().decorated("", "").to_string(),
+    }
+}
+
+/// Collects all leaf values of a table in dotted-key notation.
+fn flatten(
+    prefix: &str,
+    table: &dyn TableLike,
+    out: &mut Vec<(String, String)>,
+) {
+    for (key, item) in table.iter() {
+        let key = if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        };
+
+        match item {
+            Item::Table(table) => flatten(&key, table, out),
+            Item::Value(Value::InlineTable(table)) => {
+                flatten(&key, table, out)
+            }
+            Item::Value(value) => out.push((key, format_value(value))),
+            Item::ArrayOfTables(array) => {
+                for (idx, table) in array.iter().enumerate() {
+                    flatten(&format!("{key}.{idx}"), table, out);
+                }
+            }
+            Item::None => (),
+        }
+    }
+}
+
+/// Splits a dotted key into its parent path and the last segment.
+fn split_key(key: &str) -> DatashedResult<(Vec<&str>, &str)> {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    if parts.iter().any(|part| part.is_empty()) {
+        bail!("invalid key '{key}'");
+    }
+
+    let last = parts.pop().unwrap();
+    Ok((parts, last))
+}
+
+/// Returns the table at the given path, if it exists.
+fn get_table<'a>(
+    doc: &'a DocumentMut,
+    path: &[&str],
+) -> Option<&'a dyn TableLike> {
+    let mut table: &dyn TableLike = doc.as_table();
+    for part in path {
+        table = table.get(part)?.as_table_like()?;
+    }
+
+    Some(table)
+}
+
+/// Returns the table at the given path. Missing tables are created.
+fn get_table_mut<'a>(
+    doc: &'a mut DocumentMut,
+    path: &[&str],
+) -> DatashedResult<&'a mut dyn TableLike> {
+    let mut table: &mut dyn TableLike = doc.as_table_mut();
+    for part in path {
+        let item =
+            table.entry(part).or_insert(Item::Table(Table::new()));
+        let Some(next) = item.as_table_like_mut() else {
+            bail!("'{part}' is not a table");
+        };
+
+        table = next;
+    }
+
+    Ok(table)
+}
+
+impl Config {
+    pub(crate) fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let path = datashed.base_dir().join(Datashed::CONFIG);
+        let mut doc: DocumentMut =
+            fs::read_to_string(&path)?.parse()?;
+
+        match self.cmd {
+            ConfigCommand::Get { key, .. } => {
+                let (parents, last) = split_key(&key)?;
+                let Some(item) = get_table(&doc, &parents)
+                    .and_then(|table| table.get(last))
+                else {
+                    bail!("key '{key}' not found");
+                };
+
+                if let Some(table) = item.as_table_like() {
+                    let mut entries = vec![];
+                    flatten(&key, table, &mut entries);
+                    for (key, value) in entries {
+                        println!("{key}={value}");
+                    }
+                } else if let Some(value) = item.as_value() {
+                    println!("{}", format_value(value));
+                }
+
+                return Ok(SUCCESS);
+            }
+            ConfigCommand::List { .. } => {
+                let mut entries = vec![];
+                flatten("", doc.as_table(), &mut entries);
+                for (key, value) in entries {
+                    println!("{key}={value}");
+                }
+
+                return Ok(SUCCESS);
+            }
+            ConfigCommand::Set {
+                key, value, string, ..
+            } => {
+                let (parents, last) = split_key(&key)?;
+                let table = get_table_mut(&mut doc, &parents)?;
+                let current = table.get(last).and_then(Item::as_value);
+
+                let mut value = match current {
+                    Some(current) if current.is_str() => {
+                        Value::from(value)
+                    }
+                    _ if string => Value::from(value),
+                    _ => value
+                        .parse::<Value>()
+                        .unwrap_or_else(|_| Value::from(value)),
+                };
+
+                if let Some(current) = current {
+                    *value.decor_mut() = current.decor().clone();
+                }
+
+                table.insert(last, Item::Value(value));
+            }
+            ConfigCommand::Unset { key, .. } => {
+                let (parents, last) = split_key(&key)?;
+                let removed = get_table_mut(&mut doc, &parents)
+                    .ok()
+                    .and_then(|table| table.remove(last));
+
+                if removed.is_none() {
+                    bail!("key '{key}' not found");
+                }
+            }
+        }
+
+        let content = doc.to_string();
+        match toml_edit::de::from_str::<DatashedConfig>(&content) {
+            Ok(config) => config.metadata.validate()?,
+            Err(e) => bail!("invalid config: {}", e.message()),
+        }
+
+        fs::write(path, content)?;
+        Ok(SUCCESS)
+    }
+}
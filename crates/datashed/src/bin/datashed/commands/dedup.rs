@@ -0,0 +1,257 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::PathBuf;
+
+use datashed::{Document, MinHasher};
+use indicatif::ParallelProgressIterator;
+
+use crate::prelude::*;
+
+/// Find duplicate and near-duplicate documents
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Dedup {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// The minimum (estimated) Jaccard similarity of the word shingles
+    /// of two documents to be considered near-duplicates.
+    #[arg(short, long, default_value = "0.8")]
+    threshold: f64,
+
+    /// The number of consecutive words, which form a shingle.
+    #[arg(long, default_value = "5", value_name = "n")]
+    shingle_size: usize,
+
+    /// The number of hash functions of a MinHash signature. More hash
+    /// functions give a more accurate similarity estimate at the cost
+    /// of speed.
+    #[arg(long, default_value = "128", value_name = "n")]
+    num_hashes: usize,
+
+    /// Find only exact duplicates, i.e. documents with the same
+    /// content hash.
+    #[arg(long)]
+    exact: bool,
+
+    /// Write a keep/drop list into `filename`. The list contains all
+    /// documents of the index with the columns `path`, `cluster` and
+    /// `keep`. Of each cluster, only the first document (by path) is
+    /// kept. The output format is derived from the file extension
+    /// (`.csv`, `.tsv` or Arrow IPC otherwise).
+    #[arg(short, long, value_name = "filename")]
+    output: Option<PathBuf>,
+}
+
+const PBAR_DEDUP: &str = "Hashing documents: {human_pos} ({percent}%) | \
+        elapsed: {elapsed_precise}{msg}";
+
+/// A disjoint-set forest over the rows of the index.
+struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut idx: usize) -> usize {
+        while self.parents[idx] != idx {
+            self.parents[idx] = self.parents[self.parents[idx]];
+            idx = self.parents[idx];
+        }
+
+        idx
+    }
+
+    fn union(&mut self, lhs: usize, rhs: usize) {
+        let (lhs, rhs) = (self.find(lhs), self.find(rhs));
+        self.parents[lhs.max(rhs)] = lhs.min(rhs);
+    }
+}
+
+/// Returns the number of bands and rows per band of the LSH index. The
+/// approximate similarity threshold of the index `(1/b)^(1/r)` is the
+/// largest one, which doesn't exceed the given threshold, so that
+/// candidates are rather missed by the verification than by the index.
+fn lsh_params(num_hashes: usize, threshold: f64) -> (usize, usize) {
+    let params: Vec<_> = (1..=num_hashes)
+        .filter(|bands| num_hashes.is_multiple_of(*bands))
+        .map(|bands| {
+            let rows = num_hashes / bands;
+            let t = (1.0 / bands as f64).powf(1.0 / rows as f64);
+            (bands, rows, t)
+        })
+        .collect();
+
+    params
+        .iter()
+        .filter(|(_, _, t)| *t <= threshold)
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .or_else(|| params.iter().min_by(|a, b| a.2.total_cmp(&b.2)))
+        .map(|(bands, rows, _)| (*bands, *rows))
+        .unwrap_or((1, num_hashes))
+}
+
+impl Dedup {
+    pub(crate) fn execute(self) -> CommandResult {
+        if !(self.threshold > 0.0 && self.threshold <= 1.0) {
+            bail!("threshold must be in the range (0, 1]");
+        }
+
+        if self.num_hashes == 0 {
+            bail!("number of hash functions must be greater than 0");
+        }
+
+        let datashed = Datashed::discover()?;
+        let data_dir = datashed.data_dir();
+        let df = read_index(&datashed)?
+            .sort(["path"], SortMultipleOptions::default())?;
+
+        let paths: Vec<&str> = df
+            .column("path")?
+            .str()?
+            .iter()
+            .map(Option::unwrap_or_default)
+            .collect();
+        let hashes: Vec<&str> = df
+            .column("hash")?
+            .str()?
+            .iter()
+            .map(Option::unwrap_or_default)
+            .collect();
+
+        let mut clusters = UnionFind::new(paths.len());
+
+        // Exact duplicates share the same content hash. Only the first
+        // document of each group needs to be compared with the others.
+        let mut representatives: HashMap<&str, usize> = HashMap::new();
+        for (idx, hash) in hashes.iter().enumerate() {
+            let first = *representatives.entry(hash).or_insert(idx);
+            clusters.union(first, idx);
+        }
+
+        if !self.exact {
+            let mut rows: Vec<usize> =
+                representatives.into_values().collect();
+            rows.sort_unstable();
+
+            let hasher =
+                MinHasher::new(self.num_hashes, self.shingle_size);
+            let pbar =
+                ProgressBarBuilder::new(PBAR_DEDUP, self.common.quiet)
+                    .len(rows.len() as u64)
+                    .build();
+
+            let signatures = rows
+                .par_iter()
+                .progress_with(pbar)
+                .map(|idx| -> DatashedResult<_> {
+                    let content =
+                        Document::read(data_dir.join(paths[*idx]))?;
+                    let text = String::from_utf8_lossy(&content);
+                    Ok((*idx, hasher.signature(&text)))
+                })
+                .collect::<DatashedResult<Vec<_>>>()?;
+
+            let signatures: Vec<(usize, Vec<u64>)> = signatures
+                .into_iter()
+                .filter_map(|(idx, sig)| sig.map(|sig| (idx, sig)))
+                .collect();
+
+            let (bands, rows) =
+                lsh_params(hasher.num_hashes(), self.threshold);
+
+            for band in 0..bands {
+                let mut buckets: HashMap<u64, Vec<usize>> =
+                    HashMap::new();
+                for (pos, (_, sig)) in signatures.iter().enumerate() {
+                    let mut state = DefaultHasher::new();
+                    sig[band * rows..(band + 1) * rows]
+                        .hash(&mut state);
+                    buckets
+                        .entry(state.finish())
+                        .or_default()
+                        .push(pos);
+                }
+
+                for bucket in buckets.values().filter(|b| b.len() > 1) {
+                    for (i, lhs) in bucket.iter().enumerate() {
+                        for rhs in bucket[i + 1..].iter() {
+                            let (lhs, lsig) = &signatures[*lhs];
+                            let (rhs, rsig) = &signatures[*rhs];
+                            if clusters.find(*lhs)
+                                == clusters.find(*rhs)
+                            {
+                                continue;
+                            }
+
+                            if MinHasher::similarity(lsig, rsig)
+                                >= self.threshold
+                            {
+                                clusters.union(*lhs, *rhs);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for idx in 0..paths.len() {
+            groups.entry(clusters.find(idx)).or_default().push(idx);
+        }
+
+        groups.retain(|_, members| members.len() > 1);
+
+        let mut cluster_ids: Vec<Option<u32>> = vec![None; paths.len()];
+        let mut keep = vec![true; paths.len()];
+        let mut dropped = 0;
+
+        for (id, members) in groups.values().enumerate() {
+            let id = id as u32 + 1;
+            let exact = members
+                .iter()
+                .all(|idx| hashes[*idx] == hashes[members[0]]);
+
+            println!(
+                "cluster {id} ({})",
+                if exact { "exact" } else { "near" }
+            );
+
+            for (pos, idx) in members.iter().enumerate() {
+                cluster_ids[*idx] = Some(id);
+                if pos > 0 {
+                    keep[*idx] = false;
+                    dropped += 1;
+                }
+
+                let action = if pos == 0 { "keep" } else { "drop" };
+                println!("  {action}  {}", paths[*idx]);
+            }
+
+            println!();
+        }
+
+        if self.common.verbose {
+            eprintln!(
+                "{} clusters, {dropped} duplicate documents",
+                groups.len()
+            );
+        }
+
+        if let Some(path) = self.output {
+            let mut df = DataFrame::new(vec![
+                df.column("path")?.clone(),
+                Column::new("cluster".into(), cluster_ids),
+                Column::new("keep".into(), keep),
+            ])?;
+
+            write_df(&mut df, &path)?;
+        }
+
+        Ok(SUCCESS)
+    }
+}
@@ -0,0 +1,91 @@
+use datashed::Document;
+use indicatif::ParallelProgressIterator;
+use rayon::iter::Either;
+
+use crate::prelude::*;
+
+/// Rejoin words hyphenated at line breaks
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Dehyphenate {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// The minimum number of occurrences of a joined word in the
+    /// documents of the datashed. Hyphenated words, whose joined form
+    /// occurs less often, are kept as they are.
+    #[arg(long, default_value = "1", value_name = "n")]
+    min_count: usize,
+
+    /// Dehyphenate only the documents that match the filter
+    /// expression (see `datashed select`). The vocabulary is always
+    /// built from all documents.
+    #[arg(long, value_name = "expr")]
+    filter: Option<String>,
+
+    /// Don't modify any document, but print the number of joins,
+    /// which would be made.
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+}
+
+const PBAR_DEHYPHENATE: &str = "Dehyphenating documents: {human_pos} \
+        ({percent}%) | elapsed: {elapsed_precise}{msg}";
+
+impl Dehyphenate {
+    pub(crate) fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let data_dir = datashed.data_dir();
+        let dehyphenator = build_dehyphenator(
+            &datashed,
+            self.min_count,
+            self.common.quiet,
+        )?;
+
+        let paths = select_paths(&datashed, self.filter.as_deref())?;
+        let pbar = ProgressBarBuilder::new(
+            PBAR_DEHYPHENATE,
+            self.common.quiet,
+        )
+        .len(paths.len() as u64)
+        .build();
+
+        let results = paths
+            .par_iter()
+            .progress_with(pbar)
+            .map(|path| -> DatashedResult<_> {
+                let path_ = data_dir.join(path);
+                let Some(text) = read_plain_text(&path_)? else {
+                    return Ok(Either::Right(path));
+                };
+
+                let (text, joins) = dehyphenator.dehyphenate(&text);
+                if !self.dry_run && joins > 0 {
+                    Document::write(&path_, text.as_bytes())?;
+                }
+
+                Ok(Either::Left((path, joins)))
+            })
+            .collect::<DatashedResult<Vec<_>>>()?;
+
+        let (joins, skipped): (Vec<_>, Vec<_>) =
+            results.into_par_iter().partition_map(|result| result);
+        warn_skipped(&skipped, self.common.quiet);
+
+        let joins: Vec<_> =
+            joins.into_iter().filter(|(_, joins)| *joins > 0).collect();
+        for (path, joins) in joins.iter() {
+            println!("{path}: {joins}");
+        }
+
+        if self.common.verbose {
+            eprintln!(
+                "Joined {} words in {} of {} documents",
+                joins.iter().map(|(_, joins)| joins).sum::<usize>(),
+                joins.len(),
+                paths.len(),
+            );
+        }
+
+        Ok(SUCCESS)
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use datashed::{Dehyphenator, Document, NormalizeStep, Normalizer};
+use indicatif::{ParallelProgressIterator, ProgressBar};
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, ValueEnum)]
+enum Format {
+    /// Annif short-text corpus (`text<TAB><uri1> <uri2>`)
+    AnnifTsv,
+    /// Annif full-text corpus directory (`doc.txt` and `doc.tsv`)
+    AnnifFulltext,
+}
+
+#[derive(Debug, Clone, PartialEq, ValueEnum)]
+enum Mode {
+    Copy,
+    Hardlink,
+    Symlink,
+}
+
+/// Export documents into a corpus format for further processing
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Export {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// The format of the corpus.
+    #[arg(long, default_value = "annif-tsv")]
+    format: Format,
+
+    /// A table of subject assignments (CSV, TSV, JSON lines or Arrow
+    /// IPC), which consists of the columns `path` (the path of the
+    /// document relative to the data directory) and `subject` (the
+    /// subject URI). A document with more than one subject occurs in
+    /// multiple rows. By default, the subjects of the datashed are
+    /// used (see `datashed subjects import`).
+    #[arg(short, long, value_name = "filename")]
+    subjects: Option<PathBuf>,
+
+    /// Export only the documents that match the filter expression
+    /// (see `datashed select`).
+    #[arg(long, value_name = "expr")]
+    filter: Option<String>,
+
+    /// Truncate the text of each document to at most `n` characters.
+    #[arg(long, value_name = "n")]
+    max_length: Option<usize>,
+
+    /// Normalize the text of each document before any other
+    /// transformation by applying the given steps in order (e.g.
+    /// `historic-german,nfc`). See `datashed normalize` for a list of
+    /// available steps.
+    #[arg(
+        long,
+        value_name = "step",
+        value_delimiter = ',',
+        value_parser = str::parse::<NormalizeStep>,
+    )]
+    normalize: Vec<NormalizeStep>,
+
+    /// Rejoin words hyphenated at line breaks, if the joined word
+    /// occurs in the documents of the datashed (see `datashed
+    /// dehyphenate`).
+    #[arg(long)]
+    dehyphenate: bool,
+
+    /// Collapse any sequence of whitespace characters into a single
+    /// space and remove leading and trailing whitespace. Otherwise,
+    /// only tabs and line breaks are replaced by a space.
+    #[arg(long)]
+    normalize_whitespace: bool,
+
+    /// How to materialize the documents of a full-text corpus.
+    /// Compressed documents and documents, whose text is modified by
+    /// other options, are always written as new files.
+    #[arg(long, default_value = "copy")]
+    mode: Mode,
+
+    /// Write the corpus into `filename` instead of the standard
+    /// output. A full-text corpus requires the name of a new (or
+    /// empty) directory.
+    #[arg(short, long, value_name = "filename")]
+    output: Option<PathBuf>,
+}
+
+/// Returns the path of a document in a full-text corpus. The extension
+/// of compressed documents is removed and all documents get a `.txt`
+/// extension.
+fn fulltext_path(path: &str) -> PathBuf {
+    let path = path
+        .strip_suffix(".gz")
+        .or_else(|| path.strip_suffix(".zst"))
+        .unwrap_or(path);
+
+    Path::new(path).with_extension("txt")
+}
+
+const PBAR_EXPORT: &str = "Exporting documents: {human_pos} ({percent}%) | \
+        elapsed: {elapsed_precise}{msg}";
+
+/// Converts the subject assignments into a mapping from the document
+/// path to the list of subject URIs.
+fn subject_map(
+    df: &DataFrame,
+) -> DatashedResult<HashMap<String, Vec<String>>> {
+    let paths = df.column("path")?.cast(&DataType::String)?;
+    let subjects = df.column("subject")?.cast(&DataType::String)?;
+
+    let mut result: HashMap<String, Vec<String>> = HashMap::new();
+    for (path, subject) in
+        paths.str()?.iter().zip(subjects.str()?.iter())
+    {
+        if let (Some(path), Some(subject)) = (path, subject) {
+            let subjects = result.entry(path.into()).or_default();
+            if !subjects.iter().any(|s| s == subject) {
+                subjects.push(subject.into());
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+impl Export {
+    /// Whether the text of a document is modified or not.
+    fn is_transformed(&self) -> bool {
+        !self.normalize.is_empty()
+            || self.dehyphenate
+            || self.normalize_whitespace
+            || self.max_length.is_some()
+    }
+
+    /// Applies the transformations to the text of a document. Returns
+    /// the text and the number of rejoined words.
+    fn transform(
+        &self,
+        text: &str,
+        normalizer: Option<&Normalizer>,
+        dehyphenator: Option<&Dehyphenator>,
+    ) -> (String, usize) {
+        let text = match normalizer {
+            Some(normalizer) => normalizer.normalize(text),
+            None => text.to_string(),
+        };
+
+        let (text, joins) = match dehyphenator {
+            Some(dehyphenator) => dehyphenator.dehyphenate(&text),
+            None => (text, 0),
+        };
+
+        let mut text = if self.normalize_whitespace {
+            text.split_whitespace().collect::<Vec<_>>().join(" ")
+        } else {
+            text
+        };
+
+        if let Some(max_length) = self.max_length {
+            if let Some((idx, _)) = text.char_indices().nth(max_length)
+            {
+                text.truncate(idx);
+            }
+        }
+
+        (text, joins)
+    }
+
+    pub(crate) fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let subjects = match self.subjects {
+            Some(ref path) => subject_map(&read_df(path)?)?,
+            None => subject_map(&read_subjects(&datashed)?)?,
+        };
+
+        let paths = select_paths(&datashed, self.filter.as_deref())?;
+        let paths: Vec<_> = paths
+            .into_iter()
+            .filter_map(|path| {
+                subjects.get(&path).map(|subjects| (path, subjects))
+            })
+            .collect();
+
+        let normalizer = if !self.normalize.is_empty() {
+            Some(Normalizer::new(self.normalize.clone()))
+        } else {
+            None
+        };
+
+        let dehyphenator = if self.dehyphenate {
+            Some(build_dehyphenator(&datashed, 1, self.common.quiet)?)
+        } else {
+            None
+        };
+
+        let pbar =
+            ProgressBarBuilder::new(PBAR_EXPORT, self.common.quiet)
+                .len(paths.len() as u64)
+                .build();
+
+        let joins = match self.format {
+            Format::AnnifTsv => self.export_tsv(
+                &datashed,
+                &paths,
+                normalizer.as_ref(),
+                dehyphenator.as_ref(),
+                pbar,
+            )?,
+            Format::AnnifFulltext => self.export_fulltext(
+                &datashed,
+                &paths,
+                normalizer.as_ref(),
+                dehyphenator.as_ref(),
+                pbar,
+            )?,
+        };
+
+        if self.common.verbose {
+            eprintln!("{} documents exported", paths.len());
+            if self.dehyphenate {
+                eprintln!("{joins} hyphenated words joined");
+            }
+        }
+
+        Ok(SUCCESS)
+    }
+
+    fn export_tsv(
+        &self,
+        datashed: &Datashed,
+        paths: &[(String, &Vec<String>)],
+        normalizer: Option<&Normalizer>,
+        dehyphenator: Option<&Dehyphenator>,
+        pbar: ProgressBar,
+    ) -> DatashedResult<usize> {
+        let data_dir = datashed.data_dir();
+        let lines = paths
+            .par_iter()
+            .progress_with(pbar)
+            .map(|(path, subjects)| -> DatashedResult<_> {
+                let content = Document::read(data_dir.join(path))?;
+                let (text, joins) = self.transform(
+                    &String::from_utf8_lossy(&content),
+                    normalizer,
+                    dehyphenator,
+                );
+                let text = text.replace(['\t', '\r', '\n'], " ");
+                let subjects = subjects
+                    .iter()
+                    .map(|uri| format!("<{uri}>"))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                Ok((format!("{text}\t{subjects}\n"), joins))
+            })
+            .collect::<DatashedResult<Vec<_>>>()?;
+
+        let mut writer: Box<dyn Write> = match self.output {
+            Some(ref path) => {
+                Box::new(BufWriter::new(File::create(path)?))
+            }
+            None => Box::new(BufWriter::new(io::stdout().lock())),
+        };
+
+        for (line, _) in lines.iter() {
+            writer.write_all(line.as_bytes())?;
+        }
+
+        writer.flush()?;
+        Ok(lines.iter().map(|(_, joins)| joins).sum())
+    }
+
+    fn export_fulltext(
+        &self,
+        datashed: &Datashed,
+        paths: &[(String, &Vec<String>)],
+        normalizer: Option<&Normalizer>,
+        dehyphenator: Option<&Dehyphenator>,
+        pbar: ProgressBar,
+    ) -> DatashedResult<usize> {
+        let Some(ref out_dir) = self.output else {
+            bail!("a full-text corpus requires an output directory");
+        };
+
+        if out_dir.exists() && fs::read_dir(out_dir)?.next().is_some() {
+            bail!(
+                "output directory '{}' is not empty",
+                out_dir.display()
+            );
+        }
+
+        // Different documents (e.g. `doc.txt` and `doc.txt.gz`) can
+        // map to the same file of the corpus, which must be detected
+        // before any document is written.
+        let mut targets: HashMap<PathBuf, &str> = HashMap::new();
+        for (path, _) in paths.iter() {
+            let target = fulltext_path(path);
+            if let Some(other) = targets.insert(target.clone(), path) {
+                bail!(
+                    "documents '{other}' and '{path}' would both be \
+                    exported to '{}'",
+                    target.display()
+                );
+            }
+        }
+
+        let data_dir = datashed.data_dir();
+        let joins = paths.par_iter().progress_with(pbar).map(
+            |(path, subjects)| -> DatashedResult<usize> {
+                let src = data_dir.join(path);
+                let dst = out_dir.join(fulltext_path(path));
+                if let Some(parent) = dst.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+
+                let is_plain = path.ends_with(".txt");
+                let joins = if !is_plain || self.is_transformed() {
+                    let content = Document::read(&src)?;
+                    let text = String::from_utf8_lossy(&content);
+                    let (text, joins) =
+                        self.transform(&text, normalizer, dehyphenator);
+                    fs::write(&dst, text)?;
+                    joins
+                } else {
+                    match self.mode {
+                        Mode::Copy => {
+                            fs::copy(&src, &dst)?;
+                        }
+                        Mode::Hardlink => fs::hard_link(&src, &dst)?,
+                        Mode::Symlink => {
+                            symlink(src.canonicalize()?, &dst)?
+                        }
+                    }
+
+                    0
+                };
+
+                let mut content = String::new();
+                for uri in subjects.iter() {
+                    content.push_str(&format!("<{uri}>\n"));
+                }
+
+                fs::write(dst.with_extension("tsv"), content)?;
+                Ok(joins)
+            },
+        );
+
+        joins.try_reduce(|| 0, |lhs, rhs| Ok(lhs + rhs))
+    }
+}
@@ -1,9 +1,8 @@
-use std::fs::File;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
-use datashed::Document;
-use indicatif::ParallelProgressIterator;
-use walkdir::WalkDir;
+use datashed::{IndexOptions, IndexProgress};
+use indicatif::ProgressBar;
 
 use crate::prelude::*;
 
@@ -13,6 +12,24 @@ pub(crate) struct Index {
     #[command(flatten)]
     pub(crate) common: CommonArgs,
 
+    /// Whether to re-index all documents or not. By default, documents
+    /// whose size and modification time haven't changed since the last
+    /// run are taken from the existing index.
+    #[arg(long)]
+    full: bool,
+
+    /// A glob pattern of files to be included in the index. This
+    /// option overrides the `index.include` list of the config and
+    /// can be given multiple times.
+    #[arg(long = "include", value_name = "GLOB")]
+    include: Vec<String>,
+
+    /// A glob pattern of files to be excluded from the index. This
+    /// option overrides the `index.exclude` list of the config and
+    /// can be given multiple times.
+    #[arg(long = "exclude", value_name = "GLOB")]
+    exclude: Vec<String>,
+
     #[arg(long, short)]
     output: Option<PathBuf>,
 }
@@ -23,88 +40,77 @@ const PBAR_COLLECT: &str = "Collecting documents: {human_pos} | \
 const PBAR_INDEX: &str = "Indexing documents: {human_pos} ({percent}%) | \
         elapsed: {elapsed_precise}{msg}";
 
-#[inline(always)]
-fn is_plaintext(path: &PathBuf) -> bool {
-    path.to_str().map(|s| s.ends_with(".txt")).unwrap_or(false)
+/// Progress bars for collecting and indexing documents. The bar of
+/// the indexing step is created once the number of documents to be
+/// (re-)indexed is known.
+pub(crate) struct IndexProgressBars {
+    quiet: bool,
+    collect: ProgressBar,
+    index: OnceLock<ProgressBar>,
+}
+
+impl IndexProgressBars {
+    pub(crate) fn new(quiet: bool) -> Self {
+        Self {
+            quiet,
+            collect: ProgressBarBuilder::new(PBAR_COLLECT, quiet)
+                .build(),
+            index: OnceLock::new(),
+        }
+    }
+}
+
+impl IndexProgress for IndexProgressBars {
+    fn file_found(&self) {
+        self.collect.inc(1);
+    }
+
+    fn files_collected(&self, _count: u64) {
+        self.collect.finish_using_style();
+    }
+
+    fn indexing_started(&self, count: u64) {
+        self.index.get_or_init(|| {
+            ProgressBarBuilder::new(PBAR_INDEX, self.quiet)
+                .len(count)
+                .build()
+        });
+    }
+
+    fn document_indexed(&self) {
+        if let Some(pbar) = self.index.get() {
+            pbar.inc(1);
+        }
+    }
+
+    fn indexing_finished(&self) {
+        if let Some(pbar) = self.index.get() {
+            pbar.finish_using_style();
+        }
+    }
 }
 
 impl Index {
     pub(crate) fn execute(self) -> CommandResult {
         let datashed = Datashed::discover()?;
-        let data_dir = datashed.data_dir();
-        let base_dir = datashed.base_dir();
-
-        let pbar =
-            ProgressBarBuilder::new(PBAR_COLLECT, self.common.quiet)
-                .build();
-
-        let files = WalkDir::new(&data_dir)
-            .into_iter()
-            .filter_map(Result::ok)
-            .map(|dirent| dirent.into_path())
-            .filter(is_plaintext)
-            .progress_with(pbar)
-            .collect::<Vec<_>>();
-
-        let pbar =
-            ProgressBarBuilder::new(PBAR_INDEX, self.common.quiet)
-                .len(files.len() as u64)
-                .build();
-
-        let docs = files
-            .par_iter()
-            .progress_with(pbar)
-            .map(|path| Document::from_path(path, &data_dir))
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let mut paths: Vec<String> = vec![];
-        let mut sizes: Vec<u64> = vec![];
-
-        for doc in docs.into_iter() {
-            paths.push(doc.path);
-            sizes.push(doc.size);
-        }
+        let incremental = !self.full
+            && datashed.base_dir().join(Datashed::INDEX).is_file();
 
-        let mut df = DataFrame::new(vec![
-            Column::new("path".into(), paths),
-            Column::new("size".into(), sizes),
-        ])?
-        .lazy()
-        .select([col("*").shrink_dtype()])
-        .collect()?;
-
-        let path_str = if let Some(ref path) = self.output {
-            path.to_str().unwrap_or_default()
-        } else {
-            ""
+        let options = IndexOptions {
+            full: self.full,
+            include: self.include,
+            exclude: self.exclude,
         };
 
+        let progress = IndexProgressBars::new(self.common.quiet);
+        let mut index = datashed.index(&options, &progress)?;
+        if self.common.verbose && incremental {
+            eprintln!("Re-using {} unchanged documents", index.reused);
+        }
+
         match self.output {
-            Some(path) if path_str.ends_with(".csv") => {
-                let mut writer = CsvWriter::new(File::create(path)?);
-                writer.finish(&mut df)?;
-            }
-            Some(path) if path_str.ends_with(".tsv") => {
-                let mut writer = CsvWriter::new(File::create(path)?)
-                    .with_separator(b'\t');
-                writer.finish(&mut df)?;
-            }
-            Some(path) => {
-                let mut writer = IpcWriter::new(File::create(path)?)
-                    .with_compression(Some(IpcCompression::ZSTD))
-                    .with_parallel(true);
-
-                writer.finish(&mut df)?;
-            }
-            None => {
-                let mut writer = IpcWriter::new(File::create(
-                    base_dir.join(Datashed::INDEX),
-                )?)
-                .with_compression(Some(IpcCompression::ZSTD))
-                .with_parallel(true);
-
-                writer.finish(&mut df)?;
-            }
+            Some(path) => write_df(&mut index.df, &path)?,
+            None => datashed.write_index(&mut index.df)?,
         }
 
         Ok(SUCCESS)
@@ -4,11 +4,12 @@ use std::process::Stdio;
 use std::{env, fs, process};
 
 use clap::{Parser, ValueEnum};
+use datashed::Metadata;
 use semver::Version;
 
 use crate::prelude::*;
 
-const GITIGNORE: &'static str = "/data\n/tmp\n\n/index.ipc\n";
+const GITIGNORE: &str = "/data\n/tmp\n\n/index.ipc\n";
 
 /// Create a new datashed or re-initialize an existing one
 #[derive(Debug, Parser)]
@@ -34,6 +35,28 @@ pub(crate) struct Init {
     #[arg(short, long = "author")]
     authors: Vec<String>,
 
+    /// The license of the datashed as SPDX license expression (e.g.
+    /// `CC0-1.0`).
+    #[arg(long)]
+    license: Option<String>,
+
+    /// The URL of the homepage of the datashed.
+    #[arg(long)]
+    homepage: Option<String>,
+
+    /// The URL of the repository of the datashed.
+    #[arg(long)]
+    repository: Option<String>,
+
+    /// A list of keywords, which describe the datashed.
+    #[arg(short, long = "keyword")]
+    keywords: Vec<String>,
+
+    /// A `key=value` pair describing the sources of the documents
+    /// (e.g. `source=DNB`). This option can be given multiple times.
+    #[arg(long, value_name = "key=value", value_parser = parse_key_value)]
+    provenance: Vec<(String, String)>,
+
     /// Initialize the datashed for the given version control system
     /// (VCS).
     #[arg(long, default_value = "git")]
@@ -53,6 +76,15 @@ enum Vcs {
     None,
 }
 
+fn parse_key_value(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().into(), value.trim().into()))
+        }
+        _ => Err(format!("invalid key-value pair '{s}'")),
+    }
+}
+
 fn is_inside_git_work_tree(path: &PathBuf) -> bool {
     process::Command::new("git")
         .arg("rev-parse")
@@ -76,7 +108,7 @@ fn git_init(path: &PathBuf) -> bool {
         .unwrap_or(false)
 }
 
-fn git_get_user<P: AsRef<Path>>(path: P) -> Option<String> {
+pub(crate) fn git_get_user<P: AsRef<Path>>(path: P) -> Option<String> {
     let mut user = String::new();
 
     let result = process::Command::new("git")
@@ -168,6 +200,13 @@ impl Init {
             config.metadata.description = self.description;
             config.metadata.authors = self.authors;
             config.metadata.version = self.version;
+            config.metadata.license = self.license;
+            config.metadata.homepage = self.homepage;
+            config.metadata.repository = self.repository;
+            config.metadata.keywords = self.keywords;
+            config.metadata.provenance =
+                self.provenance.into_iter().collect();
+            config.metadata.created = Some(Metadata::now());
             config.metadata.name = self.name.unwrap_or(
                 root_dir
                     .file_name()
@@ -176,6 +215,7 @@ impl Init {
                     .to_string(),
             );
 
+            config.metadata.validate()?;
             config.save()?;
         }
 
@@ -0,0 +1,60 @@
+use datashed::Changelog;
+
+use crate::prelude::*;
+
+/// Show the changelog of the datashed
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Log {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// Limit the output to the `n` most recent releases.
+    #[arg(short = 'n', long, value_name = "n")]
+    max_count: Option<usize>,
+}
+
+impl Log {
+    pub(crate) fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let path = datashed.base_dir().join(Datashed::CHANGELOG);
+        let changelog = Changelog::from_path(path)?;
+
+        let releases = changelog
+            .releases
+            .iter()
+            .rev()
+            .take(self.max_count.unwrap_or(usize::MAX));
+
+        for (idx, release) in releases.enumerate() {
+            if idx > 0 {
+                println!();
+            }
+
+            println!("version {}", release.version);
+            if let Some(ref author) = release.author {
+                println!("Author: {author}");
+            }
+
+            println!("Date:   {}", release.date);
+            if let Some(ref stats) = release.stats {
+                println!(
+                    "Index:  {} documents, {} bytes, {} words",
+                    stats.documents, stats.size, stats.words
+                );
+            }
+
+            if let Some(ref message) = release.message {
+                println!();
+                for line in message.lines() {
+                    if line.is_empty() {
+                        println!();
+                    } else {
+                        println!("    {line}");
+                    }
+                }
+            }
+        }
+
+        Ok(SUCCESS)
+    }
+}
@@ -1,7 +1,35 @@
+pub(crate) use archive::Archive;
+pub(crate) use config::Config;
+pub(crate) use dedup::Dedup;
+pub(crate) use dehyphenate::Dehyphenate;
+pub(crate) use export::Export;
 pub(crate) use index::Index;
 pub(crate) use init::Init;
+pub(crate) use log::Log;
+pub(crate) use normalize::Normalize;
+pub(crate) use restore::Restore;
+pub(crate) use select::Select;
+pub(crate) use split::Split;
+pub(crate) use stats::Stats;
+pub(crate) use strip_boilerplate::StripBoilerplate;
+pub(crate) use subjects::Subjects;
+pub(crate) use verify::Verify;
 pub(crate) use version::Version;
 
+mod archive;
+mod config;
+mod dedup;
+mod dehyphenate;
+mod export;
 mod index;
 mod init;
+mod log;
+mod normalize;
+mod restore;
+mod select;
+mod split;
+mod stats;
+mod strip_boilerplate;
+mod subjects;
+mod verify;
 mod version;
@@ -0,0 +1,111 @@
+use datashed::{Document, NormalizeStep, Normalizer};
+use indicatif::ParallelProgressIterator;
+use rayon::iter::Either;
+
+use crate::prelude::*;
+
+/// Normalize the text of documents
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Normalize {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// The normalization steps, which are applied in the given order.
+    /// Available steps are `nfc`, `nfkc`, `controls`, `whitespace`,
+    /// `line-endings` and `historic-german`. This option overrides
+    /// the steps of the `[normalize]` section of the config.
+    #[arg(
+        short,
+        long = "step",
+        value_name = "step",
+        value_delimiter = ',',
+        value_parser = str::parse::<NormalizeStep>,
+    )]
+    steps: Vec<NormalizeStep>,
+
+    /// Normalize only the documents that match the filter expression
+    /// (see `datashed select`).
+    #[arg(long, value_name = "expr")]
+    filter: Option<String>,
+
+    /// Don't modify any document, but print the documents, which would
+    /// be changed. The command fails, if at least one document isn't
+    /// normalized.
+    #[arg(long)]
+    check: bool,
+}
+
+const PBAR_NORMALIZE: &str = "Normalizing documents: {human_pos} \
+        ({percent}%) | elapsed: {elapsed_precise}{msg}";
+
+impl Normalize {
+    pub(crate) fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let config = datashed.config()?;
+        let data_dir = datashed.data_dir();
+
+        let normalizer = if !self.steps.is_empty() {
+            Normalizer::new(self.steps.clone())
+        } else if !config.normalize.steps.is_empty() {
+            Normalizer::new(config.normalize.steps.clone())
+        } else {
+            Normalizer::default()
+        };
+
+        let paths = select_paths(&datashed, self.filter.as_deref())?;
+        let pbar =
+            ProgressBarBuilder::new(PBAR_NORMALIZE, self.common.quiet)
+                .len(paths.len() as u64)
+                .build();
+
+        let results = paths
+            .par_iter()
+            .progress_with(pbar)
+            .map(|path| -> DatashedResult<_> {
+                let path_ = data_dir.join(path);
+                let Some(text) = read_plain_text(&path_)? else {
+                    return Ok(Either::Right(path));
+                };
+
+                let normalized = normalizer.normalize(&text);
+                if normalized == text {
+                    return Ok(Either::Left(None));
+                }
+
+                if !self.check {
+                    Document::write(&path_, normalized.as_bytes())?;
+                }
+
+                Ok(Either::Left(Some(path)))
+            })
+            .collect::<DatashedResult<Vec<_>>>()?;
+
+        let (changed, skipped): (Vec<_>, Vec<_>) =
+            results.into_par_iter().partition_map(|result| result);
+        warn_skipped(&skipped, self.common.quiet);
+
+        let changed: Vec<_> = changed.into_iter().flatten().collect();
+        if self.check {
+            for path in changed.iter() {
+                println!("{path}");
+            }
+
+            return Ok(if changed.is_empty() {
+                SUCCESS
+            } else {
+                FAILURE
+            });
+        }
+
+        if self.common.verbose {
+            eprintln!(
+                "Normalized {} of {} documents (run `datashed index` \
+                to update the index)",
+                changed.len(),
+                paths.len()
+            );
+        }
+
+        Ok(SUCCESS)
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Component, Path, PathBuf};
+use std::{env, process};
+
+use indicatif::ParallelProgressIterator;
+
+use super::archive::{MANIFEST, checksum};
+use crate::prelude::*;
+
+const PBAR_VERIFY: &str = "Verifying files: {human_pos} ({percent}%) | \
+        elapsed: {elapsed_precise}{msg}";
+
+/// Unpack and verify an archive into a new datashed
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Restore {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// The archive created by `datashed archive`.
+    archive: PathBuf,
+
+    /// The location of the new datashed (default `<name>-<version>`
+    /// in the current directory). The directory must not exist or be
+    /// empty.
+    directory: Option<PathBuf>,
+}
+
+/// Splits the path of an archive entry into the top-level directory
+/// and the remaining path. Paths, which would escape the top-level
+/// directory, are rejected.
+fn split_entry_path(path: &Path) -> DatashedResult<(String, PathBuf)> {
+    let mut components = path.components();
+    let (Some(Component::Normal(prefix)), rest) =
+        (components.next(), components.as_path())
+    else {
+        bail!("invalid archive entry '{}'", path.display());
+    };
+
+    if !rest.components().all(|c| matches!(c, Component::Normal(_))) {
+        bail!("invalid archive entry '{}'", path.display());
+    }
+
+    Ok((prefix.to_string_lossy().into(), rest.into()))
+}
+
+/// A directory next to the target directory, into which the archive
+/// is unpacked. The directory is removed when it's dropped, unless it
+/// has been moved into place by [StagingDir::persist].
+struct StagingDir {
+    path: PathBuf,
+    target: PathBuf,
+    persisted: bool,
+}
+
+impl StagingDir {
+    fn new(target: PathBuf) -> DatashedResult<Self> {
+        let name = target
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or("datashed".into());
+        let parent = target.parent().unwrap_or(Path::new("."));
+        fs::create_dir_all(parent)?;
+
+        let path =
+            parent.join(format!(".{name}.{}.tmp", process::id()));
+        fs::create_dir(&path)?;
+
+        Ok(Self {
+            path,
+            target,
+            persisted: false,
+        })
+    }
+
+    /// Moves the content of the staging directory into the target
+    /// directory, which must not exist or be empty.
+    fn persist(mut self) -> DatashedResult<PathBuf> {
+        if self.target.exists() {
+            for entry in fs::read_dir(&self.path)? {
+                let entry = entry?;
+                fs::rename(
+                    entry.path(),
+                    self.target.join(entry.file_name()),
+                )?;
+            }
+
+            fs::remove_dir(&self.path)?;
+        } else {
+            fs::rename(&self.path, &self.target)?;
+        }
+
+        self.persisted = true;
+        Ok(self.target.clone())
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        if !self.persisted {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+}
+
+impl Restore {
+    pub(crate) fn execute(self) -> CommandResult {
+        let reader = BufReader::new(File::open(&self.archive)?);
+        let mut archive =
+            tar::Archive::new(zstd::Decoder::new(reader)?);
+
+        let mut staging_dir: Option<(String, StagingDir)> = None;
+        let mut files = vec![];
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let (prefix, path) = split_entry_path(&entry.path()?)?;
+
+            let (expected, staging_dir) = match staging_dir {
+                Some(ref staging_dir) => staging_dir,
+                None => {
+                    let dir = match self.directory {
+                        Some(ref dir) => env::current_dir()?.join(dir),
+                        None => env::current_dir()?.join(&prefix),
+                    };
+
+                    if dir.exists()
+                        && fs::read_dir(&dir)?.next().is_some()
+                    {
+                        bail!(
+                            "directory '{}' is not empty",
+                            dir.display()
+                        );
+                    }
+
+                    staging_dir
+                        .insert((prefix.clone(), StagingDir::new(dir)?))
+                }
+            };
+
+            if &prefix != expected {
+                bail!(
+                    "invalid archive entry '{}'",
+                    entry.path()?.display()
+                );
+            }
+
+            let dst = staging_dir.path.join(&path);
+            match entry.header().entry_type() {
+                tar::EntryType::Directory => fs::create_dir_all(&dst)?,
+                tar::EntryType::Regular => {
+                    if let Some(parent) = dst.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+
+                    entry.unpack(&dst)?;
+                    if path != Path::new(MANIFEST) {
+                        files.push(path);
+                    }
+                }
+                _ => bail!(
+                    "unsupported archive entry '{}'",
+                    entry.path()?.display()
+                ),
+            }
+        }
+
+        let Some((_, staging_dir)) = staging_dir else {
+            bail!("archive '{}' is empty", self.archive.display());
+        };
+
+        // Nothing is written into the target directory until all
+        // files have been verified.
+        let root_dir = &staging_dir.path;
+
+        let manifest_path = root_dir.join(MANIFEST);
+        if !manifest_path.is_file() {
+            bail!(
+                "archive '{}' has no manifest",
+                self.archive.display()
+            );
+        }
+
+        let mut manifest = BTreeMap::new();
+        for line in fs::read_to_string(&manifest_path)?.lines() {
+            let Some((checksum, path)) = line.split_once("  ") else {
+                bail!("invalid manifest entry '{line}'");
+            };
+
+            manifest.insert(PathBuf::from(path), checksum.to_string());
+        }
+
+        let pbar =
+            ProgressBarBuilder::new(PBAR_VERIFY, self.common.quiet)
+                .len(files.len() as u64)
+                .build();
+
+        let checksums = files
+            .par_iter()
+            .progress_with(pbar)
+            .map(|path| checksum(&root_dir.join(path)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut report = BTreeMap::new();
+        for (path, checksum) in files.into_iter().zip(checksums) {
+            match manifest.remove(&path) {
+                None => {
+                    report.insert(path, "unexpected");
+                }
+                Some(expected) if expected != checksum => {
+                    report.insert(path, "checksum mismatch");
+                }
+                Some(_) => (),
+            }
+        }
+
+        for path in manifest.into_keys() {
+            report.insert(path, "missing");
+        }
+
+        for (path, message) in report.iter() {
+            println!("{}: {message}", path.display());
+        }
+
+        if !report.is_empty() {
+            return Ok(FAILURE);
+        }
+
+        fs::create_dir_all(root_dir.join(Datashed::TMP_DIR))?;
+        fs::create_dir_all(root_dir.join(Datashed::DATA_DIR))?;
+        let root_dir = staging_dir.persist()?;
+
+        if !self.common.quiet {
+            eprintln!("Restored datashed in {}", root_dir.display());
+        }
+
+        Ok(SUCCESS)
+    }
+}
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use datashed::parse_query;
+
+use crate::prelude::*;
+
+/// Select documents from the index by a filter expression
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Select {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// Write the matching rows into `filename` instead of the standard
+    /// output. The output format is derived from the file extension
+    /// (`.csv`, `.tsv` or Arrow IPC otherwise).
+    #[arg(short, long, value_name = "filename")]
+    output: Option<PathBuf>,
+
+    /// A filter expression, e.g. `size > 1000 && lang == "deu"`.
+    /// Comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`) between columns
+    /// and literals can be combined with `&&`, `||`, `!` and
+    /// parentheses. If no expression is given, all rows are selected.
+    filter: Option<String>,
+}
+
+impl Select {
+    pub(crate) fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let mut df = read_index(&datashed)?.lazy();
+
+        if let Some(ref filter) = self.filter {
+            df = df.filter(parse_query(filter)?);
+        }
+
+        let mut df = df.collect()?;
+        if self.common.verbose {
+            eprintln!("{} documents selected", df.height());
+        }
+
+        match self.output {
+            Some(path) => write_df(&mut df, &path)?,
+            None => write_df_stdout(&mut df)?,
+        }
+
+        Ok(SUCCESS)
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+
+use crate::prelude::*;
+
+const SPLITS: [&str; 3] = ["train", "test", "validation"];
+
+/// Split the documents into train, test and validation sets
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Split {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// The ratios of the train, test and (optional) validation set.
+    /// The ratios must sum up to 1.
+    #[arg(
+        short,
+        long,
+        value_delimiter = ',',
+        default_value = "0.8,0.2"
+    )]
+    ratios: Vec<f64>,
+
+    /// The seed used to shuffle the documents. Using the same seed on
+    /// the same index always yields the same split.
+    #[arg(short, long, default_value = "0")]
+    seed: u64,
+
+    /// The name of a column of the index (e.g. `kind` or `lang`). If
+    /// set, each group of documents with the same value is split
+    /// separately by the given ratios.
+    #[arg(long, value_name = "column")]
+    stratify: Option<String>,
+
+    /// Write the split assignments into `filename` instead of the
+    /// standard output. The output format is derived from the file
+    /// extension (`.csv`, `.tsv` or Arrow IPC otherwise).
+    #[arg(short, long, value_name = "filename")]
+    output: Option<PathBuf>,
+}
+
+/// Returns a sort key of a path, which depends only on the path and
+/// the seed.
+fn sort_key(seed: u64, path: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(seed.to_le_bytes());
+    hasher.update(path.as_bytes());
+    hasher.finalize().into()
+}
+
+impl Split {
+    pub(crate) fn execute(self) -> CommandResult {
+        if !(2..=3).contains(&self.ratios.len()) {
+            bail!("expected two or three ratios");
+        }
+
+        if self.ratios.iter().any(|ratio| *ratio < 0.0) {
+            bail!("ratios must not be negative");
+        }
+
+        if (self.ratios.iter().sum::<f64>() - 1.0).abs() > 1e-9 {
+            bail!("ratios must sum up to 1");
+        }
+
+        let datashed = Datashed::discover()?;
+        let df = read_index(&datashed)?;
+        let paths = df.column("path")?.str()?;
+
+        let groups = match self.stratify {
+            Some(ref name) => {
+                let column =
+                    df.column(name)?.cast(&DataType::String)?;
+                column
+                    .str()?
+                    .iter()
+                    .map(|value| value.unwrap_or_default().to_string())
+                    .collect()
+            }
+            None => vec![String::new(); df.height()],
+        };
+
+        let mut strata: BTreeMap<&str, Vec<(usize, [u8; 32])>> =
+            BTreeMap::new();
+        for (idx, path) in paths.iter().enumerate() {
+            let Some(path) = path else {
+                bail!("invalid index entry (row {idx})");
+            };
+
+            strata
+                .entry(&groups[idx])
+                .or_default()
+                .push((idx, sort_key(self.seed, path)));
+        }
+
+        let mut splits = vec![SPLITS[0]; df.height()];
+        for rows in strata.values_mut() {
+            rows.sort_by_key(|(_, key)| *key);
+
+            let len = rows.len() as f64;
+            let mut start = 0;
+            let mut acc = 0.0;
+
+            for (split, ratio) in SPLITS.iter().zip(self.ratios.iter())
+            {
+                acc += ratio;
+                let end =
+                    ((len * acc).round() as usize).min(rows.len());
+                for (idx, _) in rows[start..end].iter() {
+                    splits[*idx] = split;
+                }
+
+                start = end;
+            }
+        }
+
+        let mut columns = vec![df.column("path")?.clone()];
+        if let Some(ref name) = self.stratify {
+            columns.push(df.column(name)?.clone());
+        }
+
+        columns.push(Column::new("split".into(), splits));
+
+        let mut df = DataFrame::new(columns)?
+            .sort(["path"], SortMultipleOptions::default())?;
+
+        if self.common.verbose {
+            for split in SPLITS.iter().take(self.ratios.len()) {
+                let count = df
+                    .column("split")?
+                    .str()?
+                    .iter()
+                    .filter(|value| value == &Some(*split))
+                    .count();
+
+                eprintln!("{split}: {count} documents");
+            }
+        }
+
+        match self.output {
+            Some(path) => write_df(&mut df, &path)?,
+            None => write_df_stdout(&mut df)?,
+        }
+
+        Ok(SUCCESS)
+    }
+}
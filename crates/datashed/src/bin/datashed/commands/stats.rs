@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::prelude::*;
+
+/// Show summary statistics of the index
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Stats {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// Print the statistics as JSON instead of a table.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct SizeStats {
+    total: u64,
+    mean: f64,
+    min: u64,
+    p25: u64,
+    median: u64,
+    p75: u64,
+    p90: u64,
+    p99: u64,
+    max: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    version: String,
+    documents: usize,
+    size: SizeStats,
+    directories: BTreeMap<String, usize>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    lang: BTreeMap<String, usize>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    kind: BTreeMap<String, usize>,
+}
+
+/// Returns the `p`-th percentile of the sorted values by the
+/// nearest-rank method.
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+impl SizeStats {
+    fn new(mut sizes: Vec<u64>) -> Self {
+        if sizes.is_empty() {
+            return Self::default();
+        }
+
+        sizes.sort_unstable();
+        let total = sizes.iter().sum::<u64>();
+
+        Self {
+            total,
+            mean: total as f64 / sizes.len() as f64,
+            min: sizes[0],
+            p25: percentile(&sizes, 25.0),
+            median: percentile(&sizes, 50.0),
+            p75: percentile(&sizes, 75.0),
+            p90: percentile(&sizes, 90.0),
+            p99: percentile(&sizes, 99.0),
+            max: sizes[sizes.len() - 1],
+        }
+    }
+}
+
+/// Counts the non-null values of a column, if the index has a column
+/// with the given name.
+fn value_counts(
+    df: &DataFrame,
+    name: &str,
+) -> DatashedResult<BTreeMap<String, usize>> {
+    let mut counts = BTreeMap::new();
+    let Ok(column) = df.column(name) else {
+        return Ok(counts);
+    };
+
+    let column = column.cast(&DataType::String)?;
+    for value in column.str()?.iter().flatten() {
+        *counts.entry(value.to_string()).or_default() += 1;
+    }
+
+    Ok(counts)
+}
+
+fn print_counts(
+    out: &mut impl Write,
+    header: &str,
+    counts: &BTreeMap<String, usize>,
+) -> io::Result<()> {
+    let width = counts
+        .keys()
+        .map(|key| key.chars().count())
+        .chain(std::iter::once(header.len()))
+        .max()
+        .unwrap_or_default();
+
+    writeln!(out, "\n{header:<width$}  documents")?;
+    for (key, count) in counts.iter() {
+        writeln!(out, "{key:<width$}  {count:>9}")?;
+    }
+
+    Ok(())
+}
+
+impl Report {
+    fn print(&self, out: &mut impl Write) -> io::Result<()> {
+        let size = &self.size;
+        let rows = [
+            ("version", self.version.clone()),
+            ("documents", self.documents.to_string()),
+            ("size (total)", size.total.to_string()),
+            ("size (mean)", format!("{:.2}", size.mean)),
+            ("size (min)", size.min.to_string()),
+            ("size (p25)", size.p25.to_string()),
+            ("size (median)", size.median.to_string()),
+            ("size (p75)", size.p75.to_string()),
+            ("size (p90)", size.p90.to_string()),
+            ("size (p99)", size.p99.to_string()),
+            ("size (max)", size.max.to_string()),
+        ];
+
+        for (key, value) in rows.iter() {
+            writeln!(out, "{key:<13}  {value}")?;
+        }
+
+        print_counts(out, "directory", &self.directories)?;
+        if !self.lang.is_empty() {
+            print_counts(out, "lang", &self.lang)?;
+        }
+
+        if !self.kind.is_empty() {
+            print_counts(out, "kind", &self.kind)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Stats {
+    pub(crate) fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let config = datashed.config()?;
+        let df = read_index(&datashed)?;
+
+        let sizes = df.column("size")?.cast(&DataType::UInt64)?;
+        let sizes: Vec<u64> = sizes.u64()?.iter().flatten().collect();
+
+        let mut directories = BTreeMap::new();
+        for path in df.column("path")?.str()?.iter().flatten() {
+            let dir = match path.split_once('/') {
+                Some((dir, _)) => dir,
+                None => ".",
+            };
+
+            *directories.entry(dir.to_string()).or_default() += 1;
+        }
+
+        let report = Report {
+            version: config.metadata.version.to_string(),
+            documents: df.height(),
+            size: SizeStats::new(sizes),
+            directories,
+            lang: value_counts(&df, "lang")?,
+            kind: value_counts(&df, "kind")?,
+        };
+
+        let mut out = io::stdout().lock();
+        if self.json {
+            serde_json::to_writer_pretty(&mut out, &report)?;
+            writeln!(out)?;
+        } else {
+            report.print(&mut out)?;
+        }
+
+        out.flush()?;
+        Ok(SUCCESS)
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use datashed::Document;
+use indicatif::ParallelProgressIterator;
+
+use crate::prelude::*;
+
+/// Remove repeated header and footer lines from documents
+#[derive(Debug, clap::Parser)]
+pub(crate) struct StripBoilerplate {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+
+    /// The minimum number of pages of a document (or the minimum
+    /// number of documents of the same directory), which must contain
+    /// a line at the top or the bottom, to be considered as
+    /// boilerplate.
+    #[arg(long, default_value = "5", value_name = "n")]
+    min_count: usize,
+
+    /// The minimum share of the pages of a document (or of the
+    /// documents of a directory), which must contain a line at the
+    /// top or the bottom, to be considered as boilerplate.
+    #[arg(long, default_value = "0.5", value_name = "share")]
+    min_share: f64,
+
+    /// Lines with more than `n` characters are never considered as
+    /// boilerplate.
+    #[arg(long, default_value = "80", value_name = "n")]
+    max_line_length: usize,
+
+    /// Strip only the documents that match the filter expression
+    /// (see `datashed select`).
+    #[arg(long, value_name = "expr")]
+    filter: Option<String>,
+
+    /// Don't modify any document, but report the lines, which would
+    /// be removed.
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+
+    /// Write the report of removed lines into `filename` instead of
+    /// the standard output. The report consists of the columns
+    /// `path`, `line` and `count`. The output format is derived from
+    /// the file extension (`.csv`, `.tsv` or Arrow IPC otherwise).
+    #[arg(short, long, value_name = "filename")]
+    report: Option<PathBuf>,
+}
+
+const PBAR_SCAN: &str = "Scanning documents: {human_pos} ({percent}%) | \
+        elapsed: {elapsed_precise}{msg}";
+
+const PBAR_STRIP: &str = "Stripping documents: {human_pos} ({percent}%) | \
+        elapsed: {elapsed_precise}{msg}";
+
+/// Returns the key of a line, which is used to find repeated lines.
+/// Whitespace is collapsed and digits are masked, so that running
+/// heads and footers with varying page numbers get the same key.
+/// Blank and long lines have no key.
+fn line_key(line: &str, max_length: usize) -> Option<String> {
+    let line = line.trim();
+    if line.is_empty() || line.chars().count() > max_length {
+        return None;
+    }
+
+    let key = line
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .map(|c| if c.is_numeric() { '#' } else { c })
+        .collect();
+
+    Some(key)
+}
+
+/// The number of non-blank lines at the top and at the bottom of each
+/// page, which are candidates for running heads and footers.
+const EDGE_LINES: usize = 2;
+
+/// Returns the indices of the lines (as split by `split_inclusive`) at
+/// the top and the bottom of each page. Pages are separated by form
+/// feeds or by at least two consecutive blank lines. A document
+/// without any page break consists of a single page.
+fn page_edges(text: &str) -> Vec<Vec<usize>> {
+    let mut pages: Vec<Vec<usize>> = vec![vec![]];
+    let mut page_break = false;
+    let mut blanks = 0;
+
+    for (idx, line) in text.split_inclusive('\n').enumerate() {
+        let (head, feed) = match line.split_once('\u{000C}') {
+            Some((head, _)) => (head, true),
+            None => (line, false),
+        };
+
+        if feed && head.trim().is_empty() {
+            page_break = true;
+        }
+
+        if line.trim().is_empty() {
+            blanks += 1;
+            page_break |= blanks >= 2;
+            continue;
+        }
+
+        if page_break && pages.last().is_some_and(|p| !p.is_empty()) {
+            pages.push(vec![]);
+        }
+
+        if let Some(page) = pages.last_mut() {
+            page.push(idx);
+        }
+
+        // A form feed after the content of a line ends the page.
+        page_break = feed && !head.trim().is_empty();
+        blanks = 0;
+    }
+
+    pages
+        .into_iter()
+        .filter(|page| !page.is_empty())
+        .map(|page| {
+            let len = page.len();
+            page.into_iter()
+                .enumerate()
+                .filter(|(pos, _)| {
+                    *pos < EDGE_LINES || pos + EDGE_LINES >= len
+                })
+                .map(|(_, idx)| idx)
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the source directory of a document.
+fn source_dir(path: &str) -> &Path {
+    Path::new(path).parent().unwrap_or(Path::new(""))
+}
+
+impl StripBoilerplate {
+    /// Returns the keys of the lines at the top and the bottom of
+    /// each page of the text.
+    fn edge_keys(&self, text: &str) -> Vec<HashSet<String>> {
+        let lines: Vec<_> = text.split_inclusive('\n').collect();
+        page_edges(text)
+            .into_iter()
+            .map(|edges| {
+                edges
+                    .into_iter()
+                    .filter_map(|idx| {
+                        line_key(lines[idx], self.max_line_length)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the keys of the lines, which occur at the top or the
+    /// bottom of enough pages of a document.
+    fn repeated_lines(
+        &self,
+        pages: &[HashSet<String>],
+    ) -> HashSet<String> {
+        let min_pages = (self.min_share * pages.len() as f64).ceil();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for key in pages.iter().flatten() {
+            *counts.entry(key).or_default() += 1;
+        }
+
+        counts
+            .into_iter()
+            .filter(|(_, count)| {
+                *count >= self.min_count && *count as f64 >= min_pages
+            })
+            .map(|(key, _)| key.to_string())
+            .collect()
+    }
+
+    /// Returns the keys of the lines of each directory, which occur
+    /// in enough documents of that directory.
+    fn shared_lines<'a>(
+        &self,
+        paths: &[&'a String],
+        keys: &[HashSet<String>],
+    ) -> HashMap<&'a Path, HashSet<String>> {
+        let mut docs: HashMap<&Path, usize> = HashMap::new();
+        let mut counts: HashMap<&Path, HashMap<&str, usize>> =
+            HashMap::new();
+
+        for (path, keys) in paths.iter().zip(keys.iter()) {
+            let dir = source_dir(path);
+            *docs.entry(dir).or_default() += 1;
+
+            let counts = counts.entry(dir).or_default();
+            for key in keys.iter() {
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+
+        counts
+            .into_iter()
+            .map(|(dir, counts)| {
+                let min_docs =
+                    (self.min_share * docs[dir] as f64).ceil();
+                let keys = counts
+                    .into_iter()
+                    .filter(|(_, count)| {
+                        *count >= self.min_count
+                            && *count as f64 >= min_docs
+                    })
+                    .map(|(key, _)| key.to_string())
+                    .collect();
+
+                (dir, keys)
+            })
+            .collect()
+    }
+
+    pub(crate) fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let data_dir = datashed.data_dir();
+
+        if !(0.0..=1.0).contains(&self.min_share) {
+            bail!("min-share must be between 0 and 1");
+        }
+
+        let paths = select_paths(&datashed, self.filter.as_deref())?;
+
+        let pbar =
+            ProgressBarBuilder::new(PBAR_SCAN, self.common.quiet)
+                .len(paths.len() as u64)
+                .build();
+
+        // The keys of all (short) lines at the top and the bottom of
+        // the pages of each document and the keys of the lines
+        // repeated within each document.
+        let scanned = paths
+            .par_iter()
+            .progress_with(pbar)
+            .map(|path| -> DatashedResult<_> {
+                let path_ = data_dir.join(path);
+                let Some(text) = read_plain_text(&path_)? else {
+                    return Ok(None);
+                };
+
+                let pages = self.edge_keys(&text);
+                let repeated = self.repeated_lines(&pages);
+                let keys = pages.into_iter().flatten().collect();
+
+                Ok(Some((keys, repeated)))
+            })
+            .collect::<DatashedResult<Vec<_>>>()?;
+
+        let mut skipped = vec![];
+        let mut plain = vec![];
+        let mut keys = vec![];
+        let mut repeated = vec![];
+
+        for (path, scanned) in paths.iter().zip(scanned) {
+            match scanned {
+                Some((k, r)) => {
+                    plain.push(path);
+                    keys.push(k);
+                    repeated.push(r);
+                }
+                None => skipped.push(path),
+            }
+        }
+
+        warn_skipped(&skipped, self.common.quiet);
+        let shared = self.shared_lines(&plain, &keys);
+        drop(keys);
+
+        let pbar =
+            ProgressBarBuilder::new(PBAR_STRIP, self.common.quiet)
+                .len(plain.len() as u64)
+                .build();
+
+        let removed = plain
+            .par_iter()
+            .zip(repeated.par_iter())
+            .progress_with(pbar)
+            .map(|(path, repeated)| -> DatashedResult<_> {
+                let shared = &shared[source_dir(path)];
+                let is_boilerplate = |key: &String| {
+                    repeated.contains(key) || shared.contains(key)
+                };
+
+                let path_ = data_dir.join(path);
+                let Some(text) = read_plain_text(&path_)? else {
+                    return Ok(vec![]);
+                };
+
+                let edges: HashSet<_> =
+                    page_edges(&text).into_iter().flatten().collect();
+                let mut result = String::with_capacity(text.len());
+                let mut removed: Vec<(String, u32)> = vec![];
+
+                for (idx, line) in
+                    text.split_inclusive('\n').enumerate()
+                {
+                    let key = if edges.contains(&idx) {
+                        line_key(line, self.max_line_length)
+                    } else {
+                        None
+                    };
+
+                    match key {
+                        Some(key) if is_boilerplate(&key) => {
+                            // Keep the page breaks of removed lines.
+                            result.extend(
+                                line.chars()
+                                    .filter(|c| *c == '\u{000C}'),
+                            );
+
+                            let line = line.trim();
+                            match removed
+                                .iter_mut()
+                                .find(|(l, _)| l == line)
+                            {
+                                Some((_, count)) => *count += 1,
+                                None => removed.push((line.into(), 1)),
+                            }
+                        }
+                        _ => result.push_str(line),
+                    }
+                }
+
+                if !self.dry_run && !removed.is_empty() {
+                    Document::write(&path_, result.as_bytes())?;
+                }
+
+                Ok(removed)
+            })
+            .collect::<DatashedResult<Vec<_>>>()?;
+
+        let mut paths_ = vec![];
+        let mut lines = vec![];
+        let mut counts = vec![];
+
+        for (path, removed) in plain.iter().zip(removed) {
+            for (line, count) in removed.into_iter() {
+                paths_.push(path.as_str());
+                lines.push(line);
+                counts.push(count);
+            }
+        }
+
+        if self.common.verbose {
+            let docs = paths_.iter().collect::<HashSet<_>>().len();
+            eprintln!(
+                "Removed {} lines from {docs} documents",
+                counts.iter().sum::<u32>(),
+            );
+        }
+
+        let mut df = DataFrame::new(vec![
+            Column::new("path".into(), paths_),
+            Column::new("line".into(), lines),
+            Column::new("count".into(), counts),
+        ])?;
+
+        match self.report {
+            Some(ref path) => write_df(&mut df, path)?,
+            None => write_df_stdout(&mut df)?,
+        }
+
+        Ok(SUCCESS)
+    }
+}
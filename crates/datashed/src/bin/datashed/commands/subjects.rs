@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use clap::Subcommand;
+
+use crate::prelude::*;
+
+/// Manage the subject assignments of the documents
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Subjects {
+    #[command(subcommand)]
+    cmd: SubjectsCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubjectsCommand {
+    Import(Import),
+    Stats(Stats),
+}
+
+/// Import subject assignments from a CSV, TSV, JSON lines or Arrow IPC
+/// file
+#[derive(Debug, clap::Parser)]
+struct Import {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// The name of the column, which contains the path of the document
+    /// relative to the data directory.
+    #[arg(long, default_value = "path", value_name = "column")]
+    key: String,
+
+    /// The name of the column, which contains the subject URI. If the
+    /// column contains a list of subjects, each subject is imported
+    /// separately.
+    #[arg(long, default_value = "subject", value_name = "column")]
+    subject: String,
+
+    /// Whether to append the assignments to the existing subjects or
+    /// not. By default, the existing subjects are replaced.
+    #[arg(short, long)]
+    append: bool,
+
+    /// The file to import.
+    filename: PathBuf,
+}
+
+/// Show statistics about the subject assignments
+#[derive(Debug, clap::Parser)]
+struct Stats {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// The number of most frequent subjects to show.
+    #[arg(long, default_value = "10", value_name = "n")]
+    top: usize,
+}
+
+impl Subjects {
+    pub(crate) fn execute(self) -> CommandResult {
+        match self.cmd {
+            SubjectsCommand::Import(cmd) => cmd.execute(),
+            SubjectsCommand::Stats(cmd) => cmd.execute(),
+        }
+    }
+}
+
+/// Removes surrounding whitespace and angle brackets from a subject.
+fn normalize_subject(subject: &str) -> &str {
+    let subject = subject.trim();
+    subject
+        .strip_prefix('<')
+        .and_then(|s| s.strip_suffix('>'))
+        .unwrap_or(subject)
+}
+
+impl Import {
+    fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let mut df = read_df(&self.filename)?;
+
+        if matches!(
+            df.column(&self.subject)?.dtype(),
+            DataType::List(_)
+        ) {
+            df = df.explode([self.subject.as_str()])?;
+        }
+
+        let keys = df.column(&self.key)?.cast(&DataType::String)?;
+        let subjects =
+            df.column(&self.subject)?.cast(&DataType::String)?;
+
+        let mut seen = HashSet::new();
+        let mut paths: Vec<String> = vec![];
+        let mut uris: Vec<String> = vec![];
+
+        if self.append
+            && datashed.base_dir().join(Datashed::SUBJECTS).is_file()
+        {
+            let df = read_subjects(&datashed)?;
+            let keys = df.column("path")?.str()?;
+            let subjects = df.column("subject")?.str()?;
+
+            for (key, subject) in keys.iter().zip(subjects.iter()) {
+                if let (Some(key), Some(subject)) = (key, subject) {
+                    if seen
+                        .insert((key.to_string(), subject.to_string()))
+                    {
+                        paths.push(key.into());
+                        uris.push(subject.into());
+                    }
+                }
+            }
+        }
+
+        let count = paths.len();
+        for (key, subject) in
+            keys.str()?.iter().zip(subjects.str()?.iter())
+        {
+            let (Some(key), Some(subject)) = (key, subject) else {
+                continue;
+            };
+
+            let (key, subject) =
+                (key.trim(), normalize_subject(subject));
+            if key.is_empty() || subject.is_empty() {
+                continue;
+            }
+
+            if seen.insert((key.to_string(), subject.to_string())) {
+                paths.push(key.into());
+                uris.push(subject.into());
+            }
+        }
+
+        if self.common.verbose {
+            eprintln!(
+                "Imported {} subject assignments",
+                paths.len() - count
+            );
+        }
+
+        let mut df = DataFrame::new(vec![
+            Column::new("path".into(), paths),
+            Column::new("subject".into(), uris),
+        ])?;
+
+        write_df(
+            &mut df,
+            &datashed.base_dir().join(Datashed::SUBJECTS),
+        )?;
+        Ok(SUCCESS)
+    }
+}
+
+impl Stats {
+    fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let df = read_subjects(&datashed)?;
+
+        let mut documents: HashMap<&str, usize> = HashMap::new();
+        let mut subjects: HashMap<&str, usize> = HashMap::new();
+
+        let paths = df.column("path")?.str()?;
+        let uris = df.column("subject")?.str()?;
+
+        for (path, subject) in paths.iter().zip(uris.iter()) {
+            if let (Some(path), Some(subject)) = (path, subject) {
+                *documents.entry(path).or_default() += 1;
+                *subjects.entry(subject).or_default() += 1;
+            }
+        }
+
+        let assignments: usize = documents.values().sum();
+        let mut report = vec![
+            ("documents", documents.len().to_string()),
+            ("assignments", assignments.to_string()),
+            ("subjects", subjects.len().to_string()),
+        ];
+
+        if let (Some(min), Some(max)) =
+            (documents.values().min(), documents.values().max())
+        {
+            let mean = assignments as f64 / documents.len() as f64;
+            report.push((
+                "subjects per document (mean)",
+                format!("{mean:.2}"),
+            ));
+            report
+                .push(("subjects per document (min)", min.to_string()));
+            report
+                .push(("subjects per document (max)", max.to_string()));
+        }
+
+        if datashed.base_dir().join(Datashed::INDEX).is_file() {
+            let index = read_index(&datashed)?;
+            let indexed: HashSet<&str> =
+                index.column("path")?.str()?.iter().flatten().collect();
+
+            let unassigned = indexed
+                .iter()
+                .filter(|path| !documents.contains_key(*path))
+                .count();
+
+            let unknown = documents
+                .keys()
+                .filter(|path| !indexed.contains(*path))
+                .count();
+
+            report.push((
+                "documents without subjects",
+                unassigned.to_string(),
+            ));
+            report.push(("unknown documents", unknown.to_string()));
+        }
+
+        let width = report.iter().map(|(key, _)| key.len()).max();
+        for (key, value) in report.iter() {
+            println!(
+                "{key:<width$}  {value}",
+                width = width.unwrap_or(0)
+            );
+        }
+
+        if self.top > 0 && !subjects.is_empty() {
+            let mut subjects: Vec<_> = subjects.into_iter().collect();
+            subjects.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+            println!("\ntop subjects:");
+            for (subject, count) in subjects.iter().take(self.top) {
+                println!("{count:>8}  {subject}");
+            }
+        }
+
+        Ok(SUCCESS)
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use datashed::{Document, PathFilter};
+use indicatif::ParallelProgressIterator;
+
+use super::index::IndexProgressBars;
+use crate::prelude::*;
+
+/// Verify the data directory against the index
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Verify {
+    #[command(flatten)]
+    pub(crate) common: CommonArgs,
+}
+
+const PBAR_VERIFY: &str = "Verifying documents: {human_pos} ({percent}%) | \
+        elapsed: {elapsed_precise}{msg}";
+
+struct Entry {
+    size: u64,
+    hash: Option<String>,
+}
+
+impl Verify {
+    pub(crate) fn execute(self) -> CommandResult {
+        let datashed = Datashed::discover()?;
+        let data_dir = datashed.data_dir();
+        let df = read_index(&datashed)?;
+        let paths = df.column("path")?.str()?.clone();
+        let sizes = df.column("size")?.cast(&DataType::UInt64)?;
+        let sizes = sizes.u64()?;
+        let hashes = match df.column("hash") {
+            Ok(column) => Some(column.str()?.clone()),
+            Err(_) => None,
+        };
+
+        let mut entries = BTreeMap::new();
+        for idx in 0..df.height() {
+            let (Some(path), Some(size)) =
+                (paths.get(idx), sizes.get(idx))
+            else {
+                bail!("invalid index entry (row {idx})");
+            };
+
+            let hash = hashes
+                .as_ref()
+                .and_then(|hashes| hashes.get(idx))
+                .map(String::from);
+
+            entries.insert(path.to_string(), Entry { size, hash });
+        }
+
+        // Files matched by the `[index]` patterns of the config, which
+        // aren't part of the index, are reported as new. Documents of
+        // the index are verified in any case, even if they have been
+        // indexed with other patterns (see `index --include`).
+        let config = datashed.config()?;
+        let filter = PathFilter::from_config(&config.index)?;
+        let files = datashed.collect_documents(
+            &filter,
+            &IndexProgressBars::new(self.common.quiet),
+        );
+
+        let mut report = BTreeMap::new();
+        for path in files.iter() {
+            let relpath = path
+                .strip_prefix(&data_dir)
+                .ok()
+                .and_then(Path::to_str)
+                .unwrap_or_default();
+
+            if !entries.contains_key(relpath) {
+                report.insert(relpath.to_string(), "new".to_string());
+            }
+        }
+
+        let pbar =
+            ProgressBarBuilder::new(PBAR_VERIFY, self.common.quiet)
+                .len(entries.len() as u64)
+                .build();
+
+        let results = entries
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .progress_with(pbar)
+            .map(|(path, entry)| -> DatashedResult<_> {
+                let path_ = data_dir.join(&path);
+                let size = match fs::metadata(&path_) {
+                    Ok(metadata) if metadata.is_file() => {
+                        metadata.len()
+                    }
+                    Ok(_) => return Ok(Some((path, "missing".into()))),
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                        return Ok(Some((path, "missing".into())));
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+                if entry.size != size {
+                    let status = format!(
+                        "size mismatch (expected {}, found {size})",
+                        entry.size
+                    );
+                    return Ok(Some((path, status)));
+                }
+
+                if let Some(hash) = entry.hash {
+                    if hash != Document::hash(&path_)? {
+                        return Ok(Some((
+                            path,
+                            "hash mismatch".into(),
+                        )));
+                    }
+                }
+
+                Ok(None)
+            })
+            .collect::<DatashedResult<Vec<_>>>()?;
+
+        report.extend(results.into_iter().flatten());
+
+        for (path, status) in report.iter() {
+            println!("{path}: {status}");
+        }
+
+        if !report.is_empty() {
+            if self.common.verbose {
+                eprintln!("{} discrepancies found", report.len());
+            }
+
+            return Ok(FAILURE);
+        }
+
+        Ok(SUCCESS)
+    }
+}
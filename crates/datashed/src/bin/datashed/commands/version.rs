@@ -1,5 +1,7 @@
+use datashed::{Changelog, Metadata, Release, ReleaseStats};
 use semver::Version as SemVer;
 
+use super::init::git_get_user;
 use crate::prelude::*;
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -11,6 +13,9 @@ enum Bump {
 
 /// Get or set the version of the datashed.
 #[derive(Debug, clap::Parser)]
+#[command(group(
+    clap::ArgGroup::new("change").args(["version", "bump"])
+))]
 pub(crate) struct Version {
     #[command(flatten)]
     pub(crate) common: CommonArgs,
@@ -29,25 +34,55 @@ pub(crate) struct Version {
     /// standard. An invalid version strings are rejected.
     #[arg(conflicts_with = "bump")]
     version: Option<SemVer>,
+
+    /// A description of the changes, which is recorded in the
+    /// changelog together with the new version.
+    #[arg(short, long, requires = "change")]
+    message: Option<String>,
+
+    /// The author of the changelog entry. By default the git identity
+    /// is used (if available).
+    #[arg(long, requires = "change")]
+    author: Option<String>,
+}
+
+/// Returns a snapshot of the index statistics, if the datashed has
+/// an index.
+fn index_stats(
+    datashed: &Datashed,
+) -> DatashedResult<Option<ReleaseStats>> {
+    if !datashed.base_dir().join(Datashed::INDEX).is_file() {
+        return Ok(None);
+    }
+
+    let df = read_index(datashed)?;
+    let sum = |name: &str| -> DatashedResult<u64> {
+        let column = df.column(name)?.cast(&DataType::UInt64)?;
+        Ok(column.u64()?.sum().unwrap_or_default())
+    };
+
+    Ok(Some(ReleaseStats {
+        documents: df.height() as u64,
+        size: sum("size")?,
+        words: sum("words")?,
+    }))
 }
 
 impl Version {
     pub(crate) fn execute(self) -> CommandResult {
         let datashed = Datashed::discover()?;
         let mut config = datashed.config()?;
+        let current = &config.metadata.version;
 
-        if let Some(version) = self.version {
-            if !self.force && version <= config.metadata.version {
-                let current = config.metadata.version.to_string();
+        let version = if let Some(version) = self.version {
+            if !self.force && version <= *current {
                 bail!("{version} must be greater than {current}");
             }
 
-            config.metadata.version = version;
-            config.save()?;
+            version
         } else if let Some(bump) = self.bump {
-            let major = config.metadata.version.major;
-            let minor = config.metadata.version.minor;
-            let patch = config.metadata.version.patch;
+            let (major, minor, patch) =
+                (current.major, current.minor, current.patch);
 
             let version = match bump {
                 Bump::Patch => SemVer::new(major, minor, patch + 1),
@@ -59,11 +94,29 @@ impl Version {
                 println!("bumped version to {version}");
             }
 
-            config.metadata.version = version;
-            config.save()?;
+            version
         } else {
-            println!("{}", config.metadata.version);
-        }
+            println!("{current}");
+            return Ok(SUCCESS);
+        };
+
+        let now = Metadata::now();
+        config.metadata.version = version.clone();
+        config.metadata.updated = Some(now);
+        config.save()?;
+
+        let release = Release {
+            version,
+            date: now,
+            author: self
+                .author
+                .or_else(|| git_get_user(datashed.base_dir())),
+            message: self.message,
+            stats: index_stats(&datashed)?,
+        };
+
+        let path = datashed.base_dir().join(Datashed::CHANGELOG);
+        Changelog::append(path, release)?;
 
         Ok(SUCCESS)
     }
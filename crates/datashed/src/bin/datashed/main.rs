@@ -10,11 +10,26 @@ pub(crate) mod cli;
 pub(crate) mod commands;
 pub(crate) mod prelude;
 pub(crate) mod progress;
+pub(crate) mod utils;
 
 fn run(args: Args) -> CommandResult {
     match *args.cmd {
+        Command::Archive(cmd) => cmd.execute(),
+        Command::Config(cmd) => cmd.execute(),
+        Command::Dedup(cmd) => cmd.execute(),
+        Command::Dehyphenate(cmd) => cmd.execute(),
+        Command::Export(cmd) => cmd.execute(),
         Command::Index(cmd) => cmd.execute(),
         Command::Init(cmd) => cmd.execute(),
+        Command::Log(cmd) => cmd.execute(),
+        Command::Normalize(cmd) => cmd.execute(),
+        Command::Restore(cmd) => cmd.execute(),
+        Command::Select(cmd) => cmd.execute(),
+        Command::Split(cmd) => cmd.execute(),
+        Command::Stats(cmd) => cmd.execute(),
+        Command::StripBoilerplate(cmd) => cmd.execute(),
+        Command::Subjects(cmd) => cmd.execute(),
+        Command::Verify(cmd) => cmd.execute(),
         Command::Version(cmd) => cmd.execute(),
     }
 }
@@ -8,8 +8,13 @@ pub(crate) use rayon::prelude::*;
 
 pub(crate) use crate::cli::CommonArgs;
 pub(crate) use crate::progress::ProgressBarBuilder;
+pub(crate) use crate::utils::{
+    build_dehyphenator, read_df, read_index, read_plain_text,
+    read_subjects, select_paths, warn_skipped, write_df,
+    write_df_stdout,
+};
 
 pub type CommandResult = DatashedResult<ExitCode>;
 
 pub(crate) const SUCCESS: ExitCode = ExitCode::SUCCESS;
-// pub(crate) const FAILURE: ExitCode = ExitCode::FAILURE;
+pub(crate) const FAILURE: ExitCode = ExitCode::FAILURE;
@@ -0,0 +1,181 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use datashed::{Dehyphenator, Document, parse_query};
+use indicatif::ParallelProgressIterator;
+
+use crate::prelude::*;
+
+/// Reads the index of the datashed.
+pub(crate) fn read_index(
+    datashed: &Datashed,
+) -> DatashedResult<DataFrame> {
+    Ok(datashed.read_index()?.collect()?)
+}
+
+/// Reads the subject table of the datashed.
+pub(crate) fn read_subjects(
+    datashed: &Datashed,
+) -> DatashedResult<DataFrame> {
+    let path = datashed.base_dir().join(Datashed::SUBJECTS);
+    if !path.is_file() {
+        bail!(
+            "subjects not found (run `datashed subjects import` first)"
+        );
+    }
+
+    Ok(IpcReader::new(File::open(path)?).finish()?)
+}
+
+/// Returns the paths of all documents of the index, which match the
+/// (optional) filter expression.
+pub(crate) fn select_paths(
+    datashed: &Datashed,
+    filter: Option<&str>,
+) -> DatashedResult<Vec<String>> {
+    let mut df = read_index(datashed)?.lazy();
+    if let Some(filter) = filter {
+        df = df.filter(parse_query(filter)?);
+    }
+
+    let df = df
+        .select([col("path")])
+        .sort(["path"], Default::default())
+        .collect()?;
+
+    Ok(df
+        .column("path")?
+        .str()?
+        .iter()
+        .flatten()
+        .map(String::from)
+        .collect())
+}
+
+/// Reads the text of a document, which is modified in place. Returns
+/// `None`, if the content isn't valid UTF-8, so that such documents
+/// are never transcoded. A byte order mark is kept as it is.
+pub(crate) fn read_plain_text(
+    path: &Path,
+) -> DatashedResult<Option<String>> {
+    Ok(String::from_utf8(Document::read(path)?).ok())
+}
+
+/// Prints a warning for each document, which has been skipped by
+/// [read_plain_text].
+pub(crate) fn warn_skipped<S: AsRef<str>>(paths: &[S], quiet: bool) {
+    if quiet {
+        return;
+    }
+
+    for path in paths.iter() {
+        eprintln!(
+            "warning: skipped {} (not valid UTF-8)",
+            path.as_ref()
+        );
+    }
+}
+
+const PBAR_VOCABULARY: &str = "Building vocabulary: {human_pos} \
+        ({percent}%) | elapsed: {elapsed_precise}{msg}";
+
+/// Builds a dehyphenator from the vocabulary of all documents of the
+/// index. Documents, which aren't plain UTF-8, are left out.
+pub(crate) fn build_dehyphenator(
+    datashed: &Datashed,
+    min_count: usize,
+    quiet: bool,
+) -> DatashedResult<Dehyphenator> {
+    let data_dir = datashed.data_dir();
+    let paths = select_paths(datashed, None)?;
+    let pbar = ProgressBarBuilder::new(PBAR_VOCABULARY, quiet)
+        .len(paths.len() as u64)
+        .build();
+
+    paths
+        .par_iter()
+        .progress_with(pbar)
+        .map(|path| -> DatashedResult<_> {
+            let mut dehyphenator = Dehyphenator::new(min_count);
+            if let Some(text) = read_plain_text(&data_dir.join(path))? {
+                dehyphenator.add_text(&text);
+            }
+
+            Ok(dehyphenator)
+        })
+        .try_reduce(
+            || Dehyphenator::new(min_count),
+            |lhs, rhs| Ok(lhs.merge(rhs)),
+        )
+}
+
+/// Reads a data frame from the given path. The input format is derived
+/// from the file extension: `.csv` and `.tsv` files are read as
+/// (tab-)separated values, `.jsonl` and `.ndjson` files are read as
+/// JSON lines, all other files are read as Arrow IPC.
+pub(crate) fn read_df(path: &Path) -> DatashedResult<DataFrame> {
+    let path_str = path.to_str().unwrap_or_default();
+
+    let df = if path_str.ends_with(".csv") || path_str.ends_with(".tsv")
+    {
+        let separator = if path_str.ends_with(".tsv") {
+            b'\t'
+        } else {
+            b','
+        };
+        CsvReadOptions::default()
+            .with_infer_schema_length(Some(0))
+            .map_parse_options(|options| {
+                options.with_separator(separator)
+            })
+            .try_into_reader_with_file_path(Some(path.into()))?
+            .finish()?
+    } else if path_str.ends_with(".jsonl")
+        || path_str.ends_with(".ndjson")
+    {
+        JsonLineReader::new(File::open(path)?).finish()?
+    } else {
+        IpcReader::new(File::open(path)?).finish()?
+    };
+
+    Ok(df)
+}
+
+/// Writes a data frame to the given path. The output format is derived
+/// from the file extension: `.csv` and `.tsv` files are written as
+/// (tab-)separated values, all other files are written as Arrow IPC.
+pub(crate) fn write_df(
+    df: &mut DataFrame,
+    path: &Path,
+) -> DatashedResult<()> {
+    let path_str = path.to_str().unwrap_or_default();
+
+    if path_str.ends_with(".csv") {
+        let mut writer = CsvWriter::new(File::create(path)?);
+        writer.finish(df)?;
+    } else if path_str.ends_with(".tsv") {
+        let mut writer =
+            CsvWriter::new(File::create(path)?).with_separator(b'\t');
+        writer.finish(df)?;
+    } else {
+        let mut writer = IpcWriter::new(File::create(path)?)
+            .with_compression(Some(IpcCompression::ZSTD))
+            .with_parallel(true);
+
+        writer.finish(df)?;
+    }
+
+    Ok(())
+}
+
+/// Writes a data frame as CSV to the standard output.
+pub(crate) fn write_df_stdout(
+    df: &mut DataFrame,
+) -> DatashedResult<()> {
+    let mut stdout = io::stdout().lock();
+    CsvWriter::new(&mut stdout).finish(df)?;
+    stdout.flush()?;
+
+    Ok(())
+}
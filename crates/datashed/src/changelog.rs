@@ -0,0 +1,89 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use toml_edit::Datetime;
+
+use crate::DatashedResult;
+
+/// The changelog of a datashed, which records an entry for each
+/// version change.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Changelog {
+    /// The releases in the order they were recorded.
+    #[serde(rename = "release", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub releases: Vec<Release>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Release {
+    /// The new version of the datashed.
+    pub version: Version,
+
+    /// The time of the version change.
+    pub date: Datetime,
+
+    /// The person, who changed the version.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub author: Option<String>,
+
+    /// A description of the changes.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub message: Option<String>,
+
+    /// A snapshot of the index statistics at the time of the release.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub stats: Option<ReleaseStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReleaseStats {
+    /// The number of documents.
+    pub documents: u64,
+
+    /// The total size of all documents in bytes.
+    pub size: u64,
+
+    /// The total number of words of all documents.
+    pub words: u64,
+}
+
+impl Changelog {
+    /// Loads the changelog from a path. A missing file is treated as
+    /// an empty changelog.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> DatashedResult<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        Ok(toml_edit::de::from_str(&content)?)
+    }
+
+    /// Appends a release to the changelog at the given path. Existing
+    /// entries are left untouched.
+    pub fn append<P: AsRef<Path>>(
+        path: P,
+        release: Release,
+    ) -> DatashedResult<()> {
+        let path = path.as_ref();
+        let changelog = Self {
+            releases: vec![release],
+        };
+
+        let mut content = toml_edit::ser::to_string_pretty(&changelog)?;
+        if path.metadata().is_ok_and(|metadata| metadata.len() > 0) {
+            content.insert(0, '\n');
+        }
+
+        let mut out =
+            OpenOptions::new().create(true).append(true).open(path)?;
+        out.write_all(content.as_bytes())?;
+
+        Ok(())
+    }
+}
@@ -1,11 +1,17 @@
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
+use anyhow::bail;
+use chrono::{SecondsFormat, Utc};
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use toml_edit::{Datetime, DocumentMut, Item, TableLike, Value};
+use url::Url;
 
 use crate::error::DatashedResult;
+use crate::normalize::NormalizeStep;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Config {
@@ -13,9 +19,25 @@ pub struct Config {
     #[serde(skip)]
     path: PathBuf,
 
+    /// The parsed TOML document of the config. It's used to preserve
+    /// comments, formatting and unknown keys on save.
+    #[serde(skip)]
+    doc: DocumentMut,
+
     /// Datashed metadata.
     pub metadata: Metadata,
 
+    /// Index configuration.
+    #[serde(default, skip_serializing_if = "IndexConfig::is_empty")]
+    pub index: IndexConfig,
+
+    /// Normalization configuration.
+    #[serde(
+        default,
+        skip_serializing_if = "NormalizeConfig::is_empty"
+    )]
+    pub normalize: NormalizeConfig,
+
     /// This structure should always be constructed using a public
     /// constructor or using the update syntax:
     ///
@@ -40,28 +62,128 @@ impl Config {
     }
 
     /// Loads an existing config from a path.
+    ///
+    /// This function fails, if the config can't be parsed or contains
+    /// invalid metadata (see [Metadata::validate]).
     pub fn from_path<P>(path: P) -> DatashedResult<Self>
     where
         P: AsRef<Path>,
     {
         let path = path.as_ref().into();
         let content = fs::read_to_string(&path)?;
-        let mut config: Self = toml_edit::de::from_str(&content)?;
+        let doc: DocumentMut = content.parse()?;
+        let mut config: Self =
+            toml_edit::de::from_document(doc.clone())?;
+        config.metadata.validate()?;
         config.path = path;
+        config.doc = doc;
 
         Ok(config)
     }
 
     /// Saves the config.
+    ///
+    /// Only the keys, whose values have been changed, are updated in
+    /// the underlying document. Comments, formatting and keys unknown
+    /// to the config are kept as they are.
     pub fn save(&self) -> DatashedResult<()> {
-        let content = toml_edit::ser::to_string_pretty(self)?;
+        let new: DocumentMut =
+            toml_edit::ser::to_string_pretty(self)?.parse()?;
+
+        // The known keys of the document as it was loaded. Only these
+        // keys are removed, if they are missing in the new document.
+        let old: DocumentMut =
+            toml_edit::de::from_document::<Self>(self.doc.clone())
+                .ok()
+                .and_then(|config| {
+                    toml_edit::ser::to_string_pretty(&config).ok()
+                })
+                .and_then(|content| content.parse().ok())
+                .unwrap_or_default();
+
+        let mut doc = self.doc.clone();
+        patch(doc.as_table_mut(), Some(old.as_table()), new.as_table());
+
         let mut out = File::create(&self.path)?;
-        out.write_all(content.as_bytes())?;
+        out.write_all(doc.to_string().as_bytes())?;
 
         Ok(())
     }
 }
 
+/// Updates `doc` with the items of `new`. Keys of `old`, which are
+/// missing in `new`, are removed from `doc`.
+fn patch(
+    doc: &mut dyn TableLike,
+    old: Option<&dyn TableLike>,
+    new: &dyn TableLike,
+) {
+    for (key, item) in new.iter() {
+        let old = old.and_then(|old| old.get(key));
+        match (doc.get_mut(key), item.as_table_like()) {
+            (Some(current), Some(table)) => {
+                if let Some(current) = current.as_table_like_mut() {
+                    let old = old.and_then(Item::as_table_like);
+                    patch(current, old, table);
+                    continue;
+                }
+            }
+            (Some(Item::Value(current)), None) => {
+                let Some(value) = item.as_value() else {
+                    continue;
+                };
+
+                if !same_value(current, value) {
+                    let decor = current.decor().clone();
+                    *current = value.clone();
+                    *current.decor_mut() = decor;
+                }
+
+                continue;
+            }
+            _ => (),
+        }
+
+        doc.insert(key, item.clone());
+    }
+
+    if let Some(old) = old {
+        for (key, _) in old.iter() {
+            if !new.contains_key(key) {
+                doc.remove(key);
+            }
+        }
+    }
+}
+
+/// Compares two values regardless of their representation.
+fn same_value(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs, rhs) {
+        (Value::String(a), Value::String(b)) => a.value() == b.value(),
+        (Value::Integer(a), Value::Integer(b)) => {
+            a.value() == b.value()
+        }
+        (Value::Float(a), Value::Float(b)) => a.value() == b.value(),
+        (Value::Boolean(a), Value::Boolean(b)) => {
+            a.value() == b.value()
+        }
+        (Value::Datetime(a), Value::Datetime(b)) => {
+            a.value() == b.value()
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len()
+                && a.iter().zip(b.iter()).all(|(a, b)| same_value(a, b))
+        }
+        (Value::InlineTable(a), Value::InlineTable(b)) => {
+            a.len() == b.len()
+                && a.iter().all(|(key, a)| {
+                    b.get(key).is_some_and(|b| same_value(a, b))
+                })
+        }
+        _ => false,
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Metadata {
     /// The name of the datashed.
@@ -77,6 +199,36 @@ pub struct Metadata {
     /// authors of the datashed.
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub authors: Vec<String>,
+
+    /// The license of the datashed as SPDX license expression (e.g.
+    /// `CC0-1.0` or `CC-BY-4.0 OR CC-BY-SA-4.0`).
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub license: Option<String>,
+
+    /// The URL of the homepage of the datashed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub homepage: Option<String>,
+
+    /// The URL of the repository of the datashed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub repository: Option<String>,
+
+    /// A list of keywords, which describe the datashed.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub keywords: Vec<String>,
+
+    /// The time the datashed was created.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub created: Option<Datetime>,
+
+    /// The time of the last version change of the datashed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub updated: Option<Datetime>,
+
+    /// A free-form table describing the sources of the documents
+    /// (e.g. `source`, `url` or `retrieved`).
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub provenance: BTreeMap<String, String>,
 }
 
 impl Default for Metadata {
@@ -86,6 +238,105 @@ impl Default for Metadata {
             description: None,
             version: Version::new(0, 1, 0),
             authors: Vec::new(),
+            license: None,
+            homepage: None,
+            repository: None,
+            keywords: Vec::new(),
+            created: None,
+            updated: None,
+            provenance: BTreeMap::new(),
+        }
+    }
+}
+
+impl Metadata {
+    /// Returns the current time as TOML datetime (in UTC).
+    pub fn now() -> Datetime {
+        Utc::now()
+            .to_rfc3339_opts(SecondsFormat::Secs, true)
+            .parse()
+            .expect("valid datetime")
+    }
+
+    /// Checks the metadata for invalid values.
+    ///
+    /// This function fails, if the license isn't a valid SPDX license
+    /// expression, the homepage or repository isn't a valid URL or a
+    /// keyword is empty.
+    pub fn validate(&self) -> DatashedResult<()> {
+        if let Some(ref license) = self.license {
+            if let Err(e) = spdx::Expression::parse(license) {
+                bail!(
+                    "invalid license expression '{license}': {}",
+                    e.reason
+                );
+            }
+        }
+
+        for (key, value) in [
+            ("homepage", &self.homepage),
+            ("repository", &self.repository),
+        ] {
+            if let Some(value) = value {
+                if let Err(e) = Url::parse(value) {
+                    bail!("invalid {key} URL '{value}': {e}");
+                }
+            }
+        }
+
+        if self
+            .keywords
+            .iter()
+            .any(|keyword| keyword.trim().is_empty())
+        {
+            bail!("keywords must not be empty");
         }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IndexConfig {
+    /// A list of glob patterns, which determine the documents of the
+    /// data directory. If the list is empty, all files with a `.txt`
+    /// extension are considered as documents.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub include: Vec<String>,
+
+    /// A list of glob patterns of files to be ignored, even if they
+    /// are matched by an include pattern.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub exclude: Vec<String>,
+
+    /// A mapping of document kinds to a list of glob patterns. The
+    /// patterns are matched against the path of a document relative
+    /// to the data directory. If a document matches the patterns of
+    /// more than one kind, the kind that comes first in lexicographic
+    /// order is chosen.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub kind: BTreeMap<String, Vec<String>>,
+}
+
+impl IndexConfig {
+    fn is_empty(&self) -> bool {
+        self.include.is_empty()
+            && self.exclude.is_empty()
+            && self.kind.is_empty()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NormalizeConfig {
+    /// The normalization steps, which are applied in the given order
+    /// (e.g. `["line-endings", "controls", "nfc"]`). If the list is
+    /// empty, the default steps are applied.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub steps: Vec<NormalizeStep>,
+}
+
+impl NormalizeConfig {
+    fn is_empty(&self) -> bool {
+        self.steps.is_empty()
     }
 }
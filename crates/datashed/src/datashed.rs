@@ -14,8 +14,10 @@ impl Datashed {
     pub const DATA_DIR: &'static str = "data";
     pub const TMP_DIR: &'static str = "tmp";
 
+    pub const CHANGELOG: &'static str = "changelog.toml";
     pub const CONFIG: &'static str = "config.toml";
     pub const INDEX: &'static str = "index.ipc";
+    pub const SUBJECTS: &'static str = "subjects.ipc";
 
     /// Discovers the root of the datashed.
     ///
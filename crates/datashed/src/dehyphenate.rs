@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+/// Characters, which mark a word broken across two lines.
+const HYPHENS: [char; 5] = ['-', '¬', '\u{00AD}', '\u{2010}', '⸗'];
+
+/// Rejoins words, which are hyphenated at a line break.
+///
+/// A word is only rejoined, if the joined form is attested in the
+/// vocabulary, which is built from the texts added by
+/// [Dehyphenator::add_text]. Thus, compounds like `Bibliotheks- und
+/// Informationswissenschaft` are kept as they are.
+#[derive(Debug, Clone)]
+pub struct Dehyphenator {
+    words: HashMap<String, usize>,
+    min_count: usize,
+}
+
+impl Default for Dehyphenator {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+/// Splits a text into words, i.e. maximal runs of alphabetic
+/// characters.
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+}
+
+/// Splits a line into its content and the line ending.
+fn split_eol(line: &str) -> (&str, &str) {
+    match line.strip_suffix("\r\n") {
+        Some(body) => (body, "\r\n"),
+        None => match line.strip_suffix('\n') {
+            Some(body) => (body, "\n"),
+            None => (line, ""),
+        },
+    }
+}
+
+impl Dehyphenator {
+    /// Creates a new dehyphenator with an empty vocabulary. A joined
+    /// word must occur at least `min_count` times in the vocabulary.
+    pub fn new(min_count: usize) -> Self {
+        Self {
+            words: HashMap::new(),
+            min_count,
+        }
+    }
+
+    /// Adds the words of a text to the vocabulary.
+    pub fn add_text(&mut self, text: &str) {
+        for word in words(text) {
+            *self.words.entry(word.to_lowercase()).or_default() += 1;
+        }
+    }
+
+    /// Merges the vocabulary of another dehyphenator into this one.
+    pub fn merge(mut self, other: Self) -> Self {
+        for (word, count) in other.words.into_iter() {
+            *self.words.entry(word).or_default() += count;
+        }
+
+        self
+    }
+
+    /// Returns the number of distinct words of the vocabulary.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true`, if the vocabulary is empty.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Whether the word is attested in the vocabulary.
+    fn is_attested(&self, word: &str) -> bool {
+        self.words
+            .get(&word.to_lowercase())
+            .is_some_and(|count| *count >= self.min_count)
+    }
+
+    /// Rejoins hyphenated line breaks. The joined word is placed at
+    /// the end of the first line (together with any punctuation
+    /// following the word). Returns the text and the number of joins.
+    pub fn dehyphenate(&self, text: &str) -> (String, usize) {
+        let mut result = String::with_capacity(text.len());
+        let mut lines = text.split_inclusive('\n').peekable();
+        let mut joins = 0;
+        let mut skip = 0;
+
+        while let Some(line) = lines.next() {
+            let (body, eol) = split_eol(line);
+            let body = &body[skip.min(body.len())..];
+            let joined = body.is_empty() && skip > 0;
+            skip = 0;
+
+            if joined {
+                // The line consisted only of the second part of a
+                // joined word.
+                continue;
+            }
+
+            let trimmed = body.trim_end();
+            let Some(head) = trimmed.strip_suffix(HYPHENS) else {
+                result.push_str(body);
+                result.push_str(eol);
+                continue;
+            };
+
+            let prefix = head
+                .rsplit(|c: char| !c.is_alphabetic())
+                .next()
+                .unwrap_or_default();
+
+            let next = lines
+                .peek()
+                .map(|next| split_eol(next).0)
+                .unwrap_or_default();
+            let token =
+                next.split_whitespace().next().unwrap_or_default();
+            let suffix = words(token).next().unwrap_or_default();
+
+            let joinable = !prefix.is_empty()
+                && !suffix.is_empty()
+                && token.starts_with(suffix)
+                && self.is_attested(&format!("{prefix}{suffix}"));
+
+            if !joinable {
+                result.push_str(body);
+                result.push_str(eol);
+                continue;
+            }
+
+            result.push_str(head);
+            result.push_str(token);
+            result.push_str(eol);
+
+            let rest = next.trim_start()[token.len()..].trim_start();
+            skip = next.len() - rest.len();
+            joins += 1;
+        }
+
+        (result, joins)
+    }
+}
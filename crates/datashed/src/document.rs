@@ -1,20 +1,123 @@
+use std::fs::{File, Metadata};
+use std::io::{self, Read, Write};
 use std::os::linux::fs::MetadataExt;
 use std::path::Path;
 
+use flate2::Compression;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use sha2::{Digest, Sha256};
+use unicode_segmentation::UnicodeSegmentation;
+use whatlang::detect;
+
 use crate::DatashedResult;
 
 pub struct Document {
     pub path: String,
     pub size: u64,
+
+    /// The size of the (decompressed) content in bytes.
+    pub uncompressed_size: u64,
+
+    pub hash: String,
+
+    /// The last modification time in nanoseconds since the epoch.
+    pub mtime: i64,
+
+    /// The number of unicode scalar values.
+    pub chars: u64,
+
+    /// The number of words according to the unicode word boundaries.
+    pub words: u64,
+
+    /// The number of lines.
+    pub lines: u64,
+
+    /// The ratio of alphabetic characters to all characters.
+    pub alpha: f64,
+
+    /// The detected language (ISO 639-3 code) of the document, if any.
+    pub lang: Option<String>,
+
+    /// The confidence score of the language detection.
+    pub lang_score: Option<f64>,
+
+    /// The kind of the document (see [KindMatcher](crate::KindMatcher)).
+    pub kind: Option<String>,
 }
 
 impl Document {
+    /// Returns the last modification time of a file in nanoseconds
+    /// since the epoch.
+    pub fn mtime(metadata: &Metadata) -> i64 {
+        metadata.st_mtime() * 1_000_000_000 + metadata.st_mtime_nsec()
+    }
+
+    /// Opens a document for reading. Files with a `.gz` or `.zst`
+    /// extension are decompressed transparently.
+    fn open(path: &Path) -> io::Result<Box<dyn Read>> {
+        let file = File::open(path)?;
+
+        Ok(match path.extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => Box::new(MultiGzDecoder::new(file)),
+            Some("zst") => Box::new(zstd::Decoder::new(file)?),
+            _ => Box::new(io::BufReader::new(file)),
+        })
+    }
+
+    /// Reads the whole content of a document. Files with a `.gz` or
+    /// `.zst` extension are decompressed transparently.
+    pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+        let mut content = vec![];
+        Self::open(path.as_ref())?.read_to_end(&mut content)?;
+        Ok(content)
+    }
+
+    /// Returns the SHA-256 hash of the (decompressed) content of a
+    /// document without reading the whole content into memory.
+    pub fn hash<P: AsRef<Path>>(path: P) -> io::Result<String> {
+        let mut hasher = Sha256::new();
+        io::copy(&mut Self::open(path.as_ref())?, &mut hasher)?;
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Writes the content of a document. Files with a `.gz` or `.zst`
+    /// extension are compressed transparently.
+    pub fn write<P: AsRef<Path>>(
+        path: P,
+        content: &[u8],
+    ) -> io::Result<()> {
+        let path = path.as_ref();
+        let file = File::create(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => {
+                let mut encoder =
+                    GzEncoder::new(file, Compression::default());
+                encoder.write_all(content)?;
+                encoder.finish()?;
+            }
+            Some("zst") => {
+                zstd::stream::copy_encode(content, file, 0)?;
+            }
+            _ => {
+                let mut writer = io::BufWriter::new(file);
+                writer.write_all(content)?;
+                writer.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn from_path<P: AsRef<Path>>(
         path: P,
         data_dir: P,
     ) -> DatashedResult<Self> {
         let path = path.as_ref().to_path_buf();
         let metadata = path.metadata()?;
+        let content = Self::read(&path)?;
+        let text = String::from_utf8_lossy(&content);
 
         let relpath = path
             .strip_prefix(data_dir)
@@ -23,9 +126,30 @@ impl Document {
             .expect("valid path")
             .into();
 
+        let chars = text.chars().count() as u64;
+        let alpha = if chars > 0 {
+            let count =
+                text.chars().filter(|c| c.is_alphabetic()).count();
+            count as f64 / chars as f64
+        } else {
+            0.0
+        };
+
+        let info = detect(&text);
+
         Ok(Self {
             path: relpath,
             size: metadata.st_size(),
+            uncompressed_size: content.len() as u64,
+            hash: format!("{:x}", Sha256::digest(&content)),
+            mtime: Self::mtime(&metadata),
+            chars,
+            words: text.unicode_words().count() as u64,
+            lines: text.lines().count() as u64,
+            alpha,
+            lang: info.as_ref().map(|info| info.lang().code().into()),
+            lang_score: info.map(|info| info.confidence()),
+            kind: None,
         })
     }
 }
@@ -0,0 +1,58 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::{DatashedResult, IndexConfig};
+
+/// Decides which files of the data directory are considered as
+/// documents.
+#[derive(Debug)]
+pub struct PathFilter {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl PathFilter {
+    /// The default include pattern, if no pattern is given.
+    pub const DEFAULT_INCLUDE: &'static str = "**/*.txt";
+
+    /// Creates a new filter from a list of include and exclude
+    /// patterns.
+    ///
+    /// This function fails, if any pattern isn't a valid glob.
+    pub fn new<S: AsRef<str>>(
+        include: &[S],
+        exclude: &[S],
+    ) -> DatashedResult<Self> {
+        let include = if include.is_empty() {
+            build_globset(&[Self::DEFAULT_INCLUDE])?
+        } else {
+            build_globset(include)?
+        };
+
+        Ok(Self {
+            include,
+            exclude: build_globset(exclude)?,
+        })
+    }
+
+    /// Creates a new filter from the `[index]` section of the config.
+    pub fn from_config(config: &IndexConfig) -> DatashedResult<Self> {
+        Self::new(&config.include, &config.exclude)
+    }
+
+    /// Returns true, if the path (relative to the data directory) is
+    /// matched by any include pattern and by no exclude pattern.
+    pub fn is_match(&self, path: &str) -> bool {
+        self.include.is_match(path) && !self.exclude.is_match(path)
+    }
+}
+
+fn build_globset<S: AsRef<str>>(
+    patterns: &[S],
+) -> DatashedResult<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns.iter() {
+        builder.add(Glob::new(pattern.as_ref())?);
+    }
+
+    Ok(builder.build()?)
+}
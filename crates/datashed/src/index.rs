@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use anyhow::bail;
+use polars::prelude::*;
+use rayon::iter::Either;
+use rayon::prelude::*;
+use walkdir::WalkDir;
+
+use crate::{
+    Datashed, DatashedResult, Document, KindMatcher, PathFilter,
+};
+
+/// Options to control how the index of a datashed is built.
+#[derive(Debug, Clone, Default)]
+pub struct IndexOptions {
+    /// Whether to re-index all documents or not. By default, documents
+    /// whose size and modification time haven't changed since the
+    /// last run are taken from the existing index.
+    pub full: bool,
+
+    /// Glob patterns of files to be included in the index. If the list
+    /// isn't empty, it overrides the `index.include` list of the
+    /// config.
+    pub include: Vec<String>,
+
+    /// Glob patterns of files to be excluded from the index. If the
+    /// list isn't empty, it overrides the `index.exclude` list of the
+    /// config.
+    pub exclude: Vec<String>,
+}
+
+/// Receives progress updates while the index of a datashed is built.
+/// All methods do nothing by default.
+pub trait IndexProgress: Sync {
+    /// Called for each file of the data directory matched by the
+    /// filter.
+    fn file_found(&self) {}
+
+    /// Called after all files of the data directory are collected.
+    fn files_collected(&self, _count: u64) {}
+
+    /// Called with the number of new or changed documents before
+    /// they are indexed.
+    fn indexing_started(&self, _count: u64) {}
+
+    /// Called for each new or changed document after it's indexed.
+    fn document_indexed(&self) {}
+
+    /// Called after all new or changed documents are indexed.
+    fn indexing_finished(&self) {}
+}
+
+/// Ignores all progress updates.
+impl IndexProgress for () {}
+
+/// The index of a datashed built by [Datashed::index].
+#[derive(Debug)]
+pub struct BuiltIndex {
+    /// The index of all documents of the data directory.
+    pub df: DataFrame,
+
+    /// The number of unchanged documents, which are taken from the
+    /// existing index.
+    pub reused: usize,
+}
+
+/// Returns the schema of the index before shrinking the data types.
+fn schema() -> Schema {
+    Schema::from_iter([
+        Field::new("path".into(), DataType::String),
+        Field::new("size".into(), DataType::UInt64),
+        Field::new("uncompressed_size".into(), DataType::UInt64),
+        Field::new("hash".into(), DataType::String),
+        Field::new(
+            "mtime".into(),
+            DataType::Datetime(TimeUnit::Nanoseconds, None),
+        ),
+        Field::new("chars".into(), DataType::UInt64),
+        Field::new("words".into(), DataType::UInt64),
+        Field::new("lines".into(), DataType::UInt64),
+        Field::new("alpha".into(), DataType::Float64),
+        Field::new("lang".into(), DataType::String),
+        Field::new("lang_score".into(), DataType::Float64),
+        Field::new("kind".into(), DataType::String),
+    ])
+}
+
+/// Loads the existing index, if it's compatible with the given schema.
+fn load_index(path: &Path, schema: &Schema) -> Option<DataFrame> {
+    let df = IpcReader::new(File::open(path).ok()?).finish().ok()?;
+    let columns = schema
+        .iter()
+        .map(|(name, dtype)| df.column(name)?.cast(dtype))
+        .collect::<PolarsResult<Vec<_>>>()
+        .ok()?;
+
+    DataFrame::new(columns).ok()
+}
+
+fn documents_to_df(docs: Vec<Document>) -> DatashedResult<DataFrame> {
+    let mut paths: Vec<String> = vec![];
+    let mut sizes: Vec<u64> = vec![];
+    let mut uncompressed_sizes: Vec<u64> = vec![];
+    let mut hashes: Vec<String> = vec![];
+    let mut mtimes: Vec<i64> = vec![];
+    let mut chars: Vec<u64> = vec![];
+    let mut words: Vec<u64> = vec![];
+    let mut lines: Vec<u64> = vec![];
+    let mut alphas: Vec<f64> = vec![];
+    let mut langs: Vec<Option<String>> = vec![];
+    let mut lang_scores: Vec<Option<f64>> = vec![];
+    let mut kinds: Vec<Option<String>> = vec![];
+
+    for doc in docs.into_iter() {
+        paths.push(doc.path);
+        sizes.push(doc.size);
+        uncompressed_sizes.push(doc.uncompressed_size);
+        hashes.push(doc.hash);
+        mtimes.push(doc.mtime);
+        chars.push(doc.chars);
+        words.push(doc.words);
+        lines.push(doc.lines);
+        alphas.push(doc.alpha);
+        langs.push(doc.lang);
+        lang_scores.push(doc.lang_score);
+        kinds.push(doc.kind);
+    }
+
+    Ok(DataFrame::new(vec![
+        Column::new("path".into(), paths),
+        Column::new("size".into(), sizes),
+        Column::new("uncompressed_size".into(), uncompressed_sizes),
+        Column::new("hash".into(), hashes),
+        Column::new("mtime".into(), mtimes)
+            .cast(&DataType::Datetime(TimeUnit::Nanoseconds, None))?,
+        Column::new("chars".into(), chars),
+        Column::new("words".into(), words),
+        Column::new("lines".into(), lines),
+        Column::new("alpha".into(), alphas),
+        Column::new("lang".into(), langs),
+        Column::new("lang_score".into(), lang_scores),
+        Column::new("kind".into(), kinds),
+    ])?)
+}
+
+impl Datashed {
+    /// Collects all files of the data directory matched by the filter.
+    pub fn collect_documents(
+        &self,
+        filter: &PathFilter,
+        progress: &dyn IndexProgress,
+    ) -> Vec<PathBuf> {
+        let data_dir = self.data_dir();
+
+        let files: Vec<_> = WalkDir::new(&data_dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|dirent| !dirent.file_type().is_dir())
+            .map(|dirent| dirent.into_path())
+            .filter(|path| {
+                path.strip_prefix(&data_dir)
+                    .ok()
+                    .and_then(Path::to_str)
+                    .is_some_and(|relpath| filter.is_match(relpath))
+            })
+            .inspect(|_| progress.file_found())
+            .collect();
+
+        progress.files_collected(files.len() as u64);
+        files
+    }
+
+    /// Builds the index of all documents of the data directory.
+    ///
+    /// Unless a full re-index is requested, unchanged documents are
+    /// taken from the existing index. The returned index isn't
+    /// written to disk (see [Datashed::write_index]).
+    pub fn index(
+        &self,
+        options: &IndexOptions,
+        progress: &dyn IndexProgress,
+    ) -> DatashedResult<BuiltIndex> {
+        let data_dir = self.data_dir();
+
+        let mut config = self.config()?;
+        if !options.include.is_empty() {
+            config.index.include = options.include.clone();
+        }
+
+        if !options.exclude.is_empty() {
+            config.index.exclude = options.exclude.clone();
+        }
+
+        let filter = PathFilter::from_config(&config.index)?;
+        let matcher = KindMatcher::new(&config.index)?;
+        let files = self.collect_documents(&filter, progress);
+
+        let mut cache = HashMap::new();
+        let previous = if !options.full {
+            load_index(&self.base_dir().join(Self::INDEX), &schema())
+        } else {
+            None
+        };
+
+        if let Some(ref df) = previous {
+            let paths = df.column("path")?.str()?;
+            let sizes = df.column("size")?.u64()?;
+            let mtimes = df.column("mtime")?.cast(&DataType::Int64)?;
+            let mtimes = mtimes.i64()?;
+
+            for idx in 0..df.height() {
+                if let (Some(path), Some(size), Some(mtime)) =
+                    (paths.get(idx), sizes.get(idx), mtimes.get(idx))
+                {
+                    cache.insert(path.to_string(), (size, mtime, idx));
+                }
+            }
+        }
+
+        let (unchanged, files): (Vec<_>, Vec<_>) =
+            files.into_par_iter().partition_map(|path| {
+                let Ok(metadata) = fs::metadata(&path) else {
+                    return Either::Right(path);
+                };
+
+                let entry = path
+                    .strip_prefix(&data_dir)
+                    .ok()
+                    .and_then(Path::to_str)
+                    .and_then(|relpath| cache.get(relpath));
+
+                match entry {
+                    Some((size, mtime, idx))
+                        if *size == metadata.len()
+                            && *mtime == Document::mtime(&metadata) =>
+                    {
+                        Either::Left(*idx as IdxSize)
+                    }
+                    _ => Either::Right(path),
+                }
+            });
+
+        progress.indexing_started(files.len() as u64);
+        let docs = files
+            .par_iter()
+            .map(|path| -> DatashedResult<Document> {
+                let mut doc = Document::from_path(path, &data_dir)?;
+                doc.kind = matcher.kind(&doc.path).map(String::from);
+                progress.document_indexed();
+                Ok(doc)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        progress.indexing_finished();
+
+        let reused = unchanged.len();
+
+        let mut df = documents_to_df(docs)?;
+        if let Some(previous) = previous {
+            let idx = IdxCa::from_vec("idx".into(), unchanged);
+            let mut reused = previous.take(&idx)?;
+
+            // The kind rules may have changed since the last run, so
+            // the kind of re-used documents is determined again.
+            let kinds: Vec<Option<&str>> = reused
+                .column("path")?
+                .str()?
+                .iter()
+                .map(|path| path.and_then(|path| matcher.kind(path)))
+                .collect();
+
+            reused.with_column(Column::new("kind".into(), kinds))?;
+            df = reused.vstack(&df)?;
+        }
+
+        let df =
+            df.lazy()
+                .select([col("*").shrink_dtype()])
+                .with_column(col("kind").cast(DataType::Categorical(
+                    None,
+                    Default::default(),
+                )))
+                .collect()?;
+
+        Ok(BuiltIndex { df, reused })
+    }
+
+    /// Reads the index of the datashed.
+    ///
+    /// This function fails, if the datashed hasn't been indexed yet.
+    pub fn read_index(&self) -> DatashedResult<LazyFrame> {
+        let path = self.base_dir().join(Self::INDEX);
+        if !path.is_file() {
+            bail!("index not found (run `datashed index` first)");
+        }
+
+        // The categorical `kind` column is read in chunks, whose local
+        // encodings would have to be re-mapped (with a warning) unless
+        // all chunks share the global string cache.
+        polars::enable_string_cache();
+        Ok(LazyFrame::scan_ipc(path, ScanArgsIpc::default())?)
+    }
+
+    /// Writes the index of the datashed.
+    pub fn write_index(
+        &self,
+        df: &mut DataFrame,
+    ) -> DatashedResult<()> {
+        let path = self.base_dir().join(Self::INDEX);
+        let mut writer = IpcWriter::new(File::create(path)?)
+            .with_compression(Some(IpcCompression::ZSTD))
+            .with_parallel(true);
+
+        writer.finish(df)?;
+        Ok(())
+    }
+}
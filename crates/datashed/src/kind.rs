@@ -0,0 +1,38 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::{DatashedResult, IndexConfig};
+
+/// Assigns a document kind to a path based on the `[index.kind]`
+/// section of the config.
+#[derive(Debug, Default)]
+pub struct KindMatcher {
+    kinds: Vec<(String, GlobSet)>,
+}
+
+impl KindMatcher {
+    /// Creates a new matcher from the given index config.
+    ///
+    /// This function fails, if any pattern isn't a valid glob.
+    pub fn new(config: &IndexConfig) -> DatashedResult<Self> {
+        let mut kinds = vec![];
+
+        for (kind, patterns) in config.kind.iter() {
+            let mut builder = GlobSetBuilder::new();
+            for pattern in patterns.iter() {
+                builder.add(Glob::new(pattern)?);
+            }
+
+            kinds.push((kind.clone(), builder.build()?));
+        }
+
+        Ok(Self { kinds })
+    }
+
+    /// Returns the kind of the first matching definition, if any.
+    pub fn kind(&self, path: &str) -> Option<&str> {
+        self.kinds
+            .iter()
+            .find(|(_, globs)| globs.is_match(path))
+            .map(|(kind, _)| kind.as_str())
+    }
+}
@@ -1,9 +1,25 @@
+mod changelog;
 mod config;
 mod datashed;
+mod dehyphenate;
 mod document;
 mod error;
+mod filter;
+mod index;
+mod kind;
+mod minhash;
+mod normalize;
+mod query;
 
-pub use config::Config;
+pub use changelog::{Changelog, Release, ReleaseStats};
+pub use config::{Config, IndexConfig, Metadata, NormalizeConfig};
 pub use datashed::Datashed;
+pub use dehyphenate::Dehyphenator;
 pub use document::Document;
 pub use error::DatashedResult;
+pub use filter::PathFilter;
+pub use index::{BuiltIndex, IndexOptions, IndexProgress};
+pub use kind::KindMatcher;
+pub use minhash::MinHasher;
+pub use normalize::{NormalizeStep, Normalizer};
+pub use query::parse_query;
@@ -0,0 +1,76 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Computes MinHash signatures of texts over word shingles.
+///
+/// The signatures of two texts agree in each position with a
+/// probability equal to the Jaccard similarity of their shingle sets.
+#[derive(Debug)]
+pub struct MinHasher {
+    shingle_size: usize,
+    seeds: Vec<u64>,
+}
+
+/// A fast, well-distributed mixing function (SplitMix64 finalizer).
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+impl MinHasher {
+    /// Creates a new hasher with `num_hashes` hash functions over
+    /// shingles of `shingle_size` consecutive words.
+    pub fn new(num_hashes: usize, shingle_size: usize) -> Self {
+        let seeds = (0..num_hashes as u64).map(mix).collect();
+        Self {
+            shingle_size: shingle_size.max(1),
+            seeds,
+        }
+    }
+
+    /// Returns the number of hash functions.
+    pub fn num_hashes(&self) -> usize {
+        self.seeds.len()
+    }
+
+    /// Returns the MinHash signature of a text. Texts with fewer words
+    /// than the shingle size consist of a single shingle. A text
+    /// without any words has no signature.
+    pub fn signature(&self, text: &str) -> Option<Vec<u64>> {
+        let words: Vec<String> =
+            text.unicode_words().map(str::to_lowercase).collect();
+        if words.is_empty() {
+            return None;
+        }
+
+        let size = self.shingle_size.min(words.len());
+        let mut signature = vec![u64::MAX; self.seeds.len()];
+
+        for shingle in words.windows(size) {
+            let mut hasher = DefaultHasher::new();
+            shingle.hash(&mut hasher);
+            let hash = hasher.finish();
+
+            for (min, seed) in
+                signature.iter_mut().zip(self.seeds.iter())
+            {
+                *min = (*min).min(mix(hash ^ seed));
+            }
+        }
+
+        Some(signature)
+    }
+
+    /// Estimates the Jaccard similarity of two signatures.
+    pub fn similarity(lhs: &[u64], rhs: &[u64]) -> f64 {
+        if lhs.is_empty() {
+            return 0.0;
+        }
+
+        let count = lhs.iter().zip(rhs.iter()).filter(|(a, b)| a == b);
+        count.count() as f64 / lhs.len() as f64
+    }
+}
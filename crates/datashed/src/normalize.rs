@@ -0,0 +1,319 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::DatashedResult;
+
+/// A single step of a normalization chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NormalizeStep {
+    /// Unicode canonical composition (NFC).
+    Nfc,
+
+    /// Unicode compatibility composition (NFKC).
+    Nfkc,
+
+    /// Removes control characters (except tabs and line breaks) and
+    /// invisible format characters like zero-width spaces, soft
+    /// hyphens and byte order marks.
+    Controls,
+
+    /// Collapses any sequence of horizontal whitespace into a single
+    /// space and removes trailing whitespace of each line.
+    Whitespace,
+
+    /// Converts `\r\n` and `\r` line endings into `\n`.
+    LineEndings,
+
+    /// Maps historic German characters (long s, r rotunda, combining
+    /// e above), typographic ligatures and some common historic
+    /// spellings to their modern equivalents. This covers spellings
+    /// abolished by the reform of 1901 (e.g. `seyn` or `Theil`) as
+    /// well as by the reform of 1996 (e.g. `daß` or `Kenntniß`).
+    HistoricGerman,
+}
+
+impl NormalizeStep {
+    /// The steps, which are applied, if no steps are configured.
+    pub const DEFAULT: [Self; 3] =
+        [Self::LineEndings, Self::Controls, Self::Nfc];
+
+    /// Returns the name of the step.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Nfc => "nfc",
+            Self::Nfkc => "nfkc",
+            Self::Controls => "controls",
+            Self::Whitespace => "whitespace",
+            Self::LineEndings => "line-endings",
+            Self::HistoricGerman => "historic-german",
+        }
+    }
+
+    /// Applies the step to a text.
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Self::Nfc => text.nfc().collect(),
+            Self::Nfkc => text.nfkc().collect(),
+            Self::Controls => {
+                text.chars().filter(|c| !is_removable(*c)).collect()
+            }
+            Self::Whitespace => collapse_whitespace(text),
+            Self::LineEndings => {
+                text.replace("\r\n", "\n").replace('\r', "\n")
+            }
+            Self::HistoricGerman => {
+                modernize_spellings(&modernize_chars(text))
+            }
+        }
+    }
+}
+
+impl fmt::Display for NormalizeStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for NormalizeStep {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "nfc" => Ok(Self::Nfc),
+            "nfkc" => Ok(Self::Nfkc),
+            "controls" => Ok(Self::Controls),
+            "whitespace" => Ok(Self::Whitespace),
+            "line-endings" => Ok(Self::LineEndings),
+            "historic-german" => Ok(Self::HistoricGerman),
+            _ => Err(anyhow!("invalid normalization step '{s}'")),
+        }
+    }
+}
+
+/// Whether a character is removed by [NormalizeStep::Controls].
+fn is_removable(c: char) -> bool {
+    match c {
+        '\t' | '\n' | '\r' => false,
+        '\u{00AD}' | '\u{200B}' | '\u{2060}' | '\u{FEFF}' => true,
+        c => c.is_control(),
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for (idx, line) in text.split('\n').enumerate() {
+        if idx > 0 {
+            result.push('\n');
+        }
+
+        let mut words = line
+            .split(|c: char| c.is_whitespace())
+            .filter(|w| !w.is_empty());
+        let leading = line.starts_with(char::is_whitespace)
+            && !line.trim().is_empty();
+        if leading {
+            result.push(' ');
+        }
+
+        if let Some(word) = words.next() {
+            result.push_str(word);
+            for word in words {
+                result.push(' ');
+                result.push_str(word);
+            }
+        }
+    }
+
+    result
+}
+
+/// The combining latin small letter e (U+0364), which denotes an
+/// umlaut in historic German prints (e.g. `aͤ` for `ä`).
+const COMBINING_E: char = '\u{0364}';
+
+/// Historic spellings and their modern equivalents (in lower case).
+/// The table contains spellings abolished by the reform of 1901 (e.g.
+/// `seyn`, `theil`) as well as by the reform of 1996 (e.g. `daß`,
+/// `muß`).
+const SPELLINGS: [(&str, &str); 35] = [
+    ("antheil", "anteil"),
+    ("bey", "bei"),
+    ("dabey", "dabei"),
+    ("daß", "dass"),
+    ("drey", "drei"),
+    ("frey", "frei"),
+    ("freyheit", "freiheit"),
+    ("gieng", "ging"),
+    ("giebt", "gibt"),
+    ("hülfe", "hilfe"),
+    ("läßt", "lässt"),
+    ("muß", "muss"),
+    ("muth", "mut"),
+    ("noth", "not"),
+    ("roth", "rot"),
+    ("seyd", "seid"),
+    ("sey", "sei"),
+    ("seyn", "sein"),
+    ("thal", "tal"),
+    ("theil", "teil"),
+    ("theile", "teile"),
+    ("theilen", "teilen"),
+    ("theils", "teils"),
+    ("thier", "tier"),
+    ("thiere", "tiere"),
+    ("thun", "tun"),
+    ("thür", "tür"),
+    ("thüre", "türe"),
+    ("thut", "tut"),
+    ("todt", "tot"),
+    ("urtheil", "urteil"),
+    ("werth", "wert"),
+    ("wißt", "wisst"),
+    ("wußte", "wusste"),
+    ("zwey", "zwei"),
+];
+
+/// Replaces historic characters and ligatures by their modern
+/// equivalents.
+fn modernize_chars(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            'ſ' => result.push('s'),
+            'ꝛ' => result.push('r'),
+            'Ꝛ' => result.push('R'),
+            'ꝰ' => result.push_str("us"),
+            'ﬀ' => result.push_str("ff"),
+            'ﬁ' => result.push_str("fi"),
+            'ﬂ' => result.push_str("fl"),
+            'ﬃ' => result.push_str("ffi"),
+            'ﬄ' => result.push_str("ffl"),
+            'ﬅ' | 'ﬆ' => result.push_str("st"),
+            '⸗' => result.push('-'),
+            COMBINING_E => {
+                let umlaut = match result.chars().last() {
+                    Some('a') => Some('ä'),
+                    Some('o') => Some('ö'),
+                    Some('u') => Some('ü'),
+                    Some('A') => Some('Ä'),
+                    Some('O') => Some('Ö'),
+                    Some('U') => Some('Ü'),
+                    _ => None,
+                };
+
+                if let Some(umlaut) = umlaut {
+                    result.pop();
+                    result.push(umlaut);
+                }
+            }
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Converts the first character of a word to upper case.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Returns the modern spelling of a word or `None`, if the spelling of
+/// the word hasn't changed. The case of the word is preserved.
+fn modern_spelling(word: &str) -> Option<String> {
+    let lower = word.to_lowercase();
+    let modern = match SPELLINGS.iter().find(|(old, _)| *old == lower) {
+        Some((_, modern)) => modern.to_string(),
+        None => format!("{}nis", lower.strip_suffix("niß")?),
+    };
+
+    if word == lower {
+        Some(modern)
+    } else if word == capitalize(&lower) {
+        Some(capitalize(&modern))
+    } else if word == word.to_uppercase() {
+        Some(modern.to_uppercase())
+    } else {
+        None
+    }
+}
+
+/// Replaces historic spellings of words by their modern spelling.
+fn modernize_spellings(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut start = None;
+
+    for (idx, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (start, c.is_alphabetic()) {
+            (None, true) => start = Some(idx),
+            (Some(begin), false) => {
+                let word = &text[begin..idx];
+                match modern_spelling(word) {
+                    Some(modern) => result.push_str(&modern),
+                    None => result.push_str(word),
+                }
+
+                start = None;
+            }
+            _ => (),
+        }
+
+        if start.is_none() && idx < text.len() {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Applies a chain of normalization steps to texts.
+#[derive(Debug, Clone)]
+pub struct Normalizer {
+    steps: Vec<NormalizeStep>,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self::new(NormalizeStep::DEFAULT.to_vec())
+    }
+}
+
+impl Normalizer {
+    /// Creates a new normalizer, which applies the steps in the given
+    /// order.
+    pub fn new(steps: Vec<NormalizeStep>) -> Self {
+        Self { steps }
+    }
+
+    /// Creates a new normalizer from a comma-separated list of steps
+    /// (e.g. `nfc,controls`).
+    pub fn parse(steps: &str) -> DatashedResult<Self> {
+        let steps = steps
+            .split(',')
+            .map(|step| step.trim().parse())
+            .collect::<DatashedResult<Vec<_>>>()?;
+
+        Ok(Self::new(steps))
+    }
+
+    /// Returns the steps of the normalizer.
+    pub fn steps(&self) -> &[NormalizeStep] {
+        &self.steps
+    }
+
+    /// Normalizes a text by applying all steps in order.
+    pub fn normalize(&self, text: &str) -> String {
+        self.steps
+            .iter()
+            .fold(text.to_string(), |text, step| step.apply(&text))
+    }
+}
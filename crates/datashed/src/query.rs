@@ -0,0 +1,300 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use anyhow::{anyhow, bail};
+use polars::prelude::*;
+
+use crate::DatashedResult;
+
+/// Parses a filter expression into a polars expression.
+///
+/// A filter expression consists of comparisons between columns and
+/// literals (`==`, `!=`, `<`, `<=`, `>`, `>=`), which can be combined
+/// with the logical operators `&&`, `||` and `!` as well as
+/// parentheses. Literals are either numbers, strings enclosed in
+/// single or double quotes, `true`, `false` or `null`.
+///
+/// ```
+/// use datashed::parse_query;
+///
+/// let expr = parse_query("size > 1000 && lang == \"deu\"");
+/// assert!(expr.is_ok());
+/// ```
+pub fn parse_query(input: &str) -> DatashedResult<Expr> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+
+    if let Some(token) = parser.peek() {
+        bail!("unexpected token '{token}' in filter expression");
+    }
+
+    match expr {
+        Operand::Expr(expr) => Ok(expr),
+        Operand::Column(name) => Ok(col(name)),
+        _ => bail!("filter expression must not be a literal"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ident(s) => write!(f, "{s}"),
+            Self::Str(s) => write!(f, "\"{s}\""),
+            Self::Int(i) => write!(f, "{i}"),
+            Self::Float(x) => write!(f, "{x}"),
+            Self::Op(op) => write!(f, "{op}"),
+            Self::LParen => write!(f, "("),
+            Self::RParen => write!(f, ")"),
+        }
+    }
+}
+
+const OPERATORS: [&str; 9] =
+    ["==", "!=", "<=", ">=", "&&", "||", "<", ">", "!"];
+
+fn tokenize(input: &str) -> DatashedResult<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars: Peekable<CharIndices> =
+        input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' {
+            chars.next();
+            tokens.push(Token::LParen);
+        } else if c == ')' {
+            chars.next();
+            tokens.push(Token::RParen);
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, c)) => value.push(c),
+                        None => bail!("unterminated string literal"),
+                    },
+                    Some((_, ch)) if ch == c => break,
+                    Some((_, ch)) => value.push(ch),
+                    None => bail!("unterminated string literal"),
+                }
+            }
+
+            tokens.push(Token::Str(value));
+        } else if c.is_ascii_digit()
+            || (c == '-'
+                && input[start + 1..]
+                    .starts_with(|c: char| c.is_ascii_digit()))
+        {
+            let mut end = start + c.len_utf8();
+            chars.next();
+
+            while let Some(&(idx, c)) = chars.peek() {
+                if c.is_ascii_digit() || c == '.' || c == '_' {
+                    end = idx + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            let value = input[start..end].replace('_', "");
+            if let Ok(value) = value.parse::<i64>() {
+                tokens.push(Token::Int(value));
+            } else if let Ok(value) = value.parse::<f64>() {
+                tokens.push(Token::Float(value));
+            } else {
+                bail!("invalid number '{value}'");
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(idx, c)) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' {
+                    end = idx + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            tokens.push(Token::Ident(input[start..end].into()));
+        } else {
+            let rest = &input[start..];
+            let Some(op) =
+                OPERATORS.iter().find(|op| rest.starts_with(*op))
+            else {
+                bail!(
+                    "unexpected character '{c}' in filter expression"
+                );
+            };
+
+            for _ in 0..op.len() {
+                chars.next();
+            }
+
+            tokens.push(Token::Op(op));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Creates a literal with a fixed data type. Unlike [lit], numbers
+/// don't become dynamically typed literals, which can't be coerced to
+/// the type of the column without the `dtype-i128` feature.
+fn typed_lit<T: Into<Scalar>>(value: T) -> Expr {
+    Expr::Literal(value.into().into())
+}
+
+#[derive(Debug)]
+enum Operand {
+    Column(String),
+    Literal(Expr),
+    Null,
+    Expr(Expr),
+}
+
+impl Operand {
+    fn into_expr(self) -> Expr {
+        match self {
+            Self::Column(name) => col(name),
+            Self::Literal(expr) => expr,
+            Self::Null => lit(NULL),
+            Self::Expr(expr) => expr,
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> DatashedResult<Operand> {
+        let mut lhs = self.parse_and()?;
+        while self.peek() == Some(&Token::Op("||")) {
+            self.next();
+            let rhs = self.parse_and()?;
+            lhs = Operand::Expr(lhs.into_expr().or(rhs.into_expr()));
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> DatashedResult<Operand> {
+        let mut lhs = self.parse_not()?;
+        while self.peek() == Some(&Token::Op("&&")) {
+            self.next();
+            let rhs = self.parse_not()?;
+            lhs = Operand::Expr(lhs.into_expr().and(rhs.into_expr()));
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_not(&mut self) -> DatashedResult<Operand> {
+        if self.peek() == Some(&Token::Op("!")) {
+            self.next();
+            let expr = self.parse_not()?;
+            return Ok(Operand::Expr(expr.into_expr().not()));
+        }
+
+        self.parse_cmp()
+    }
+
+    fn parse_cmp(&mut self) -> DatashedResult<Operand> {
+        let lhs = self.parse_primary()?;
+        let op = match self.peek() {
+            Some(Token::Op(op))
+                if ["==", "!=", "<", "<=", ">", ">="].contains(op) =>
+            {
+                *op
+            }
+            _ => return Ok(lhs),
+        };
+
+        self.next();
+        let rhs = self.parse_primary()?;
+
+        let expr = match (op, lhs, rhs) {
+            ("==", Operand::Null, operand)
+            | ("==", operand, Operand::Null) => {
+                operand.into_expr().is_null()
+            }
+            ("!=", Operand::Null, operand)
+            | ("!=", operand, Operand::Null) => {
+                operand.into_expr().is_not_null()
+            }
+            (_, Operand::Null, _) | (_, _, Operand::Null) => {
+                bail!("null can only be compared with '==' or '!='")
+            }
+            (op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.into_expr(), rhs.into_expr());
+                match op {
+                    "==" => lhs.eq(rhs),
+                    "!=" => lhs.neq(rhs),
+                    "<" => lhs.lt(rhs),
+                    "<=" => lhs.lt_eq(rhs),
+                    ">" => lhs.gt(rhs),
+                    ">=" => lhs.gt_eq(rhs),
+                    _ => unreachable!(),
+                }
+            }
+        };
+
+        Ok(Operand::Expr(expr))
+    }
+
+    fn parse_primary(&mut self) -> DatashedResult<Operand> {
+        let token = self.next().ok_or_else(|| {
+            anyhow!("unexpected end of filter expression")
+        })?;
+
+        Ok(match token {
+            Token::Ident(name) => match name.as_str() {
+                "true" => Operand::Literal(lit(true)),
+                "false" => Operand::Literal(lit(false)),
+                "null" => Operand::Null,
+                _ => Operand::Column(name),
+            },
+            Token::Str(value) => Operand::Literal(lit(value)),
+            Token::Int(value) => Operand::Literal(typed_lit(value)),
+            Token::Float(value) => Operand::Literal(typed_lit(value)),
+            Token::LParen => {
+                let expr = self.parse_or()?;
+                if self.next() != Some(Token::RParen) {
+                    bail!("missing closing parenthesis");
+                }
+
+                expr
+            }
+            token => {
+                bail!("unexpected token '{token}' in filter expression")
+            }
+        })
+    }
+}
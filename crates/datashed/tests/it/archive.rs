@@ -0,0 +1,136 @@
+use std::fs;
+
+use crate::prelude::*;
+
+fn create_archive(datashed_dir: &TempDir) -> TestResult {
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(datashed_dir)
+        .args(["archive", "-q", "-o", "shed.tar.zst"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn archive_restore() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+    create_archive(&datashed_dir)?;
+
+    let target_dir = TempDir::new()?;
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&target_dir)
+        .arg("restore")
+        .arg(datashed_dir.join("shed.tar.zst"))
+        .arg("restored")
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::starts_with("Restored datashed"));
+
+    let restored = target_dir.join("restored");
+    for path in ["config.toml", "index.ipc", "data/0/dnb.txt"] {
+        assert_eq!(
+            fs::read(datashed_dir.join(path))?,
+            fs::read(restored.join(path))?
+        );
+    }
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd.current_dir(&restored).arg("verify").assert();
+    assert.success().code(0).stdout(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn archive_default_name() -> TestResult {
+    let datashed_dir = create_datashed()?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["archive", "-q"])
+        .assert();
+    assert.success().code(0);
+
+    let config =
+        Config::from_path(datashed_dir.join(Datashed::CONFIG))?;
+    let name = format!("{}-0.1.0.tar.zst", config.metadata.name);
+    assert!(datashed_dir.join(&name).is_file());
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["archive", "-q"])
+        .assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stderr(predicates::str::contains("already exists"));
+
+    Ok(())
+}
+
+#[test]
+fn restore_non_empty_directory() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_archive(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["restore", "shed.tar.zst", "data"])
+        .assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stderr(predicates::str::contains("is not empty"));
+
+    Ok(())
+}
+
+#[test]
+fn restore_tampered_archive() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_archive(&datashed_dir)?;
+
+    let archive = datashed_dir.join("shed.tar.zst");
+    let mut tar = zstd::decode_all(fs::read(&archive)?.as_slice())?;
+    let needle = &fs::read(data_dir().join("dnb.txt"))?[..16];
+    let pos = tar
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .unwrap();
+    tar[pos..pos + needle.len()].fill(b'x');
+    fs::write(&archive, zstd::encode_all(tar.as_slice(), 0)?)?;
+
+    let target_dir = TempDir::new()?;
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&target_dir)
+        .arg("restore")
+        .arg(&archive)
+        .arg("restored")
+        .assert();
+
+    assert.failure().code(1).stdout(predicates::str::contains(
+        "data/0/dnb.txt: checksum mismatch",
+    ));
+
+    assert!(fs::read_dir(&target_dir)?.next().is_none());
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use std::fs;
+
+use crate::prelude::*;
+
+fn config(
+    datashed_dir: &TempDir,
+    args: &[&str],
+) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("datashed").unwrap();
+    cmd.current_dir(datashed_dir)
+        .arg("config")
+        .args(args)
+        .assert()
+}
+
+#[test]
+fn config_get_set() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let path = datashed_dir.join(Datashed::CONFIG);
+
+    let content = fs::read_to_string(&path)?;
+    fs::write(&path, format!("# datashed config\n{content}"))?;
+
+    config(&datashed_dir, &["set", "metadata.description", "foo bar"])
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    config(&datashed_dir, &["get", "metadata.description"])
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq("foo bar\n"));
+
+    config(&datashed_dir, &["set", "index.include", "[\"**/*.txt\"]"])
+        .success()
+        .code(0);
+
+    let config = Config::from_path(&path)?;
+    assert_eq!(config.metadata.description, Some("foo bar".into()));
+    assert_eq!(config.index.include, vec!["**/*.txt".to_string()]);
+    assert!(
+        fs::read_to_string(&path)?.starts_with("# datashed config\n")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn config_list_unset() -> TestResult {
+    let datashed_dir = create_datashed()?;
+
+    config(&datashed_dir, &["set", "metadata.name", "foo"]).success();
+    config(&datashed_dir, &["set", "metadata.description", "bar"])
+        .success();
+    config(&datashed_dir, &["unset", "metadata.description"]).success();
+    config(&datashed_dir, &["set", "metadata.authors", "[\"baz\"]"])
+        .success();
+    config(&datashed_dir, &["unset", "metadata.authors"]).success();
+
+    config(&datashed_dir, &["list"]).success().code(0).stdout(
+        predicates::str::starts_with(
+            "metadata.name=foo\n\
+             metadata.version=0.1.0\n\
+             metadata.created=",
+        ),
+    );
+
+    config(&datashed_dir, &["unset", "metadata.description"])
+        .failure()
+        .code(1)
+        .stderr(predicates::str::contains(
+            "key 'metadata.description' not found",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn config_set_invalid() -> TestResult {
+    let datashed_dir = create_datashed()?;
+
+    config(&datashed_dir, &["set", "metadata.version", "1.0"])
+        .failure()
+        .code(1)
+        .stderr(predicates::str::contains("invalid config"));
+
+    config(&datashed_dir, &["unset", "metadata.name"])
+        .failure()
+        .code(1)
+        .stderr(predicates::str::contains("invalid config"));
+
+    let config =
+        Config::from_path(datashed_dir.join(Datashed::CONFIG))?;
+    assert_eq!(config.metadata.version.to_string(), "0.1.0");
+
+    Ok(())
+}
+
+#[test]
+fn config_from_path_invalid_metadata() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let path = datashed_dir.join(Datashed::CONFIG);
+    let content = fs::read_to_string(&path)?;
+
+    for (key, value, message) in [
+        ("license", "Foo License", "invalid license expression"),
+        ("homepage", "example.org", "invalid homepage URL"),
+    ] {
+        fs::write(
+            &path,
+            content.replace(
+                "[metadata]\n",
+                &format!("[metadata]\n{key} = \"{value}\"\n"),
+            ),
+        )?;
+
+        let err = Config::from_path(&path).unwrap_err();
+        assert!(err.to_string().contains(message));
+
+        // An invalid config can still be fixed with `datashed config`.
+        config(&datashed_dir, &["unset", &format!("metadata.{key}")])
+            .success();
+        Config::from_path(&path)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::fs;
+
+use crate::prelude::*;
+
+fn create_duplicates(datashed_dir: &TempDir) -> TestResult {
+    let data_dir = datashed_dir.join("data");
+    fs::copy(data_dir.join("0/dnb.txt"), data_dir.join("1/dnb.txt"))?;
+
+    let mut text = fs::read_to_string(data_dir.join("0/tib.txt"))?;
+    text.push_str("Some additional words at the end.\n");
+    fs::write(data_dir.join("1/tib.txt"), text)?;
+
+    Ok(())
+}
+
+#[test]
+fn dedup_near() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_duplicates(&datashed_dir)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["dedup", "-q", "-o", "dedup.csv"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq(
+            "cluster 1 (exact)\n  \
+               keep  0/dnb.txt\n  \
+               drop  1/dnb.txt\n\
+             \n\
+             cluster 2 (near)\n  \
+               keep  0/tib.txt\n  \
+               drop  1/tib.txt\n\
+             \n",
+        ))
+        .stderr(predicates::str::is_empty());
+
+    assert_eq!(
+        fs::read_to_string(datashed_dir.join("dedup.csv"))?,
+        "path,cluster,keep\n\
+         0/dnb.txt,1,true\n\
+         0/tib.txt,2,true\n\
+         1/dnb.txt,1,false\n\
+         1/tib.txt,2,false\n\
+         1/zbw.txt,,true\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dedup_exact() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_duplicates(&datashed_dir)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["dedup", "--exact"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq(
+            "cluster 1 (exact)\n  \
+               keep  0/dnb.txt\n  \
+               drop  1/dnb.txt\n\
+             \n",
+        ))
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn dedup_invalid_threshold() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["dedup", "--threshold", "1.5"])
+        .assert();
+
+    assert.failure().code(1).stderr(predicates::str::contains(
+        "threshold must be in the range (0, 1]",
+    ));
+
+    Ok(())
+}
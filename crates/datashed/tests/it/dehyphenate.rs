@@ -0,0 +1,100 @@
+use std::fs;
+
+use crate::prelude::*;
+
+fn create_hyphenated_documents(datashed_dir: &TempDir) -> TestResult {
+    let data_dir = datashed_dir.join("data/2");
+    fs::create_dir(&data_dir)?;
+
+    fs::write(
+        data_dir.join("a.txt"),
+        "Die Biblio-\nthek ist groß. Die Bibliothek\nhat Bücher, Zeit-\n\
+         schriften und Bibliotheks-\nund Informations-\nwissenschaft.\n\
+         Eine neue Biblio-\nthek.\n",
+    )?;
+    fs::write(data_dir.join("b.txt"), "Zeitschriften sind da.\n")?;
+
+    Ok(())
+}
+
+#[test]
+fn dehyphenate() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_hyphenated_documents(&datashed_dir)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["dehyphenate", "-v"])
+        .assert()
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq("2/a.txt: 3\n"))
+        .stderr(predicates::str::contains(
+            "Joined 3 words in 1 of 5 documents",
+        ));
+
+    assert_eq!(
+        fs::read_to_string(datashed_dir.join("data/2/a.txt"))?,
+        "Die Bibliothek\nist groß. Die Bibliothek\nhat Bücher, \
+         Zeitschriften\nund Bibliotheks-\nund Informations-\n\
+         wissenschaft.\nEine neue Bibliothek.\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dehyphenate_dry_run() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_hyphenated_documents(&datashed_dir)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["dehyphenate", "-q", "-n", "--min-count", "2"])
+        .assert()
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq("2/a.txt: 2\n"));
+
+    let content =
+        fs::read_to_string(datashed_dir.join("data/2/a.txt"))?;
+    assert!(content.starts_with("Die Biblio-\nthek ist groß."));
+
+    Ok(())
+}
+
+#[test]
+fn dehyphenate_skip_non_utf8() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_hyphenated_documents(&datashed_dir)?;
+    let data_dir = datashed_dir.join("data/2");
+    let content = b"Eine gro\xdfe Biblio-\nthek.\n";
+    fs::write(data_dir.join("latin1.txt"), content)?;
+    fs::write(
+        data_dir.join("bom.txt"),
+        "\u{FEFF}Eine Biblio-\nthek.\n",
+    )?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["dehyphenate"])
+        .assert()
+        .success()
+        .code(0)
+        .stdout(predicates::str::contains("2/a.txt: 3\n"))
+        .stdout(predicates::str::contains("2/bom.txt: 1\n"))
+        .stderr(predicates::str::contains(
+            "warning: skipped 2/latin1.txt (not valid UTF-8)",
+        ));
+
+    assert_eq!(fs::read(data_dir.join("latin1.txt"))?, content);
+    assert_eq!(
+        fs::read_to_string(data_dir.join("bom.txt"))?,
+        "\u{FEFF}Eine Bibliothek.\n"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,244 @@
+use std::fs;
+
+use crate::prelude::*;
+
+fn create_subjects(datashed_dir: &TempDir) -> TestResult {
+    fs::write(
+        datashed_dir.join("subjects.csv"),
+        "path,subject\n\
+         0/dnb.txt,http://d-nb.info/gnd/2\n\
+         0/dnb.txt,http://d-nb.info/gnd/1\n\
+         0/dnb.txt,http://d-nb.info/gnd/1\n\
+         1/zbw.txt,http://d-nb.info/gnd/3\n",
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn export_annif_tsv() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+    create_subjects(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["export", "--format", "annif-tsv", "-s", "subjects.csv"])
+        .args(["--max-length", "30"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq(
+            "Die Deutsche Nationalbibliothe\t\
+             <http://d-nb.info/gnd/2> <http://d-nb.info/gnd/1>\n\
+             The National Library of Econom\t\
+             <http://d-nb.info/gnd/3>\n",
+        ))
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn export_annif_tsv_output() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+    create_subjects(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["export", "-s", "subjects.csv", "-o", "corpus.tsv"])
+        .args(["--filter", "lang == 'deu'", "--normalize-whitespace"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    let corpus = fs::read_to_string(datashed_dir.join("corpus.tsv"))?;
+    assert_eq!(corpus.lines().count(), 1);
+
+    let (text, subjects) = corpus.trim_end().split_once('\t').unwrap();
+    assert!(text.starts_with("Die Deutsche Nationalbibliothek (DNB)"));
+    assert!(!text.contains("  "));
+    assert!(!text.contains('\n'));
+    assert_eq!(
+        subjects,
+        "<http://d-nb.info/gnd/2> <http://d-nb.info/gnd/1>"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn export_annif_tsv_normalize() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    fs::write(
+        datashed_dir.join("data/1/zbw.txt"),
+        "Der Beſtand der Bibliothek\twird ſeit 1919 erweitert.\n",
+    )?;
+    create_index(&datashed_dir)?;
+    create_subjects(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["export", "-s", "subjects.csv", "--filter"])
+        .args(["path == '1/zbw.txt'", "--normalize", "historic-german"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq(
+            "Der Bestand der Bibliothek wird seit 1919 erweitert. \t\
+             <http://d-nb.info/gnd/3>\n",
+        ))
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn export_annif_fulltext_dehyphenate() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    fs::write(
+        datashed_dir.join("data/1/zbw.txt"),
+        "Die Biblio-\nthek der Wirtschaftswissenschaften.\n",
+    )?;
+    fs::write(datashed_dir.join("data/1/bib.txt"), "Bibliothek\n")?;
+    create_index(&datashed_dir)?;
+    create_subjects(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["export", "--format", "annif-fulltext", "-v"])
+        .args(["-s", "subjects.csv", "-o", "corpus", "--dehyphenate"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::contains("1 hyphenated words joined"));
+
+    assert_eq!(
+        fs::read_to_string(datashed_dir.join("corpus/1/zbw.txt"))?,
+        "Die Bibliothek\nder Wirtschaftswissenschaften.\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn export_annif_fulltext() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+    create_subjects(&datashed_dir)?;
+
+    for mode in ["copy", "hardlink", "symlink"] {
+        let mut cmd = Command::cargo_bin("datashed")?;
+        let assert = cmd
+            .current_dir(&datashed_dir)
+            .args(["export", "--format", "annif-fulltext"])
+            .args(["-s", "subjects.csv", "-o", mode, "--mode", mode])
+            .assert();
+
+        assert
+            .success()
+            .code(0)
+            .stdout(predicates::str::is_empty())
+            .stderr(predicates::str::is_empty());
+
+        let out_dir = datashed_dir.join(mode);
+        assert_eq!(
+            fs::read_to_string(out_dir.join("0/dnb.txt"))?,
+            fs::read_to_string(data_dir().join("dnb.txt"))?
+        );
+        assert_eq!(
+            fs::read_to_string(out_dir.join("0/dnb.tsv"))?,
+            "<http://d-nb.info/gnd/2>\n<http://d-nb.info/gnd/1>\n"
+        );
+        assert_eq!(
+            fs::read_to_string(out_dir.join("1/zbw.tsv"))?,
+            "<http://d-nb.info/gnd/3>\n"
+        );
+        assert!(!out_dir.join("0/tib.txt").exists());
+        assert_eq!(
+            out_dir.join("0/dnb.txt").symlink_metadata()?.is_symlink(),
+            mode == "symlink"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn export_annif_fulltext_non_empty_dir() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+    create_subjects(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["export", "--format", "annif-fulltext"])
+        .args(["-s", "subjects.csv", "-o", "data"])
+        .assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::ord::eq(
+            "error: output directory 'data' is not empty\n",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn export_annif_fulltext_conflicting_paths() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let data_dir = datashed_dir.join("data");
+    fs::write(data_dir.join("1/zbw.md"), "# ZBW")?;
+
+    let config = datashed_dir.join(Datashed::CONFIG);
+    let mut content = fs::read_to_string(&config)?;
+    content.push_str("\n[index]\n");
+    content.push_str("include = [\"**/*.txt\", \"**/*.md\"]\n");
+    fs::write(&config, content)?;
+    create_index(&datashed_dir)?;
+
+    fs::write(
+        datashed_dir.join("subjects.csv"),
+        "path,subject\n\
+         1/zbw.txt,http://d-nb.info/gnd/1\n\
+         1/zbw.md,http://d-nb.info/gnd/2\n",
+    )?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["export", "--format", "annif-fulltext"])
+        .args(["-s", "subjects.csv", "-o", "corpus"])
+        .assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::contains(
+            "would both be exported to '1/zbw.txt'",
+        ));
+
+    assert!(!datashed_dir.join("corpus/1").exists());
+    Ok(())
+}
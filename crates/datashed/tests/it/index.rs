@@ -1,6 +1,9 @@
-use std::fs::File;
+use std::fs::{self, File};
+use std::io::Write;
 use std::path::Path;
 
+use flate2::Compression;
+use flate2::write::GzEncoder;
 use polars::io::SerReader;
 use polars::prelude::*;
 
@@ -22,22 +25,71 @@ fn check_index<P: AsRef<Path>>(path: P) -> TestResult {
     let df = df.sort(["path"], SortMultipleOptions::default())?;
     assert_eq!(df.height(), 3);
 
-    let columns = df.take_columns();
-    let paths: Vec<_> = columns[0].str()?.iter().collect();
-    let sizes: Vec<_> =
-        columns[1].cast(&DataType::UInt64)?.u64()?.iter().collect();
+    let column = |name: &str, dtype: &DataType| {
+        df.column(name).and_then(|column| column.cast(dtype))
+    };
+
+    let paths = column("path", &DataType::String)?;
+    let paths: Vec<_> = paths.str()?.iter().collect();
+    let sizes = column("size", &DataType::UInt64)?;
+    let sizes: Vec<_> = sizes.u64()?.iter().collect();
+    let hashes = column("hash", &DataType::String)?;
+    let hashes: Vec<_> = hashes.str()?.iter().collect();
+    let chars = column("chars", &DataType::UInt64)?;
+    let chars: Vec<_> = chars.u64()?.iter().collect();
+    let words = column("words", &DataType::UInt64)?;
+    let words: Vec<_> = words.u64()?.iter().collect();
+    let lines = column("lines", &DataType::UInt64)?;
+    let lines: Vec<_> = lines.u64()?.iter().collect();
+    let alpha = column("alpha", &DataType::Float64)?;
+    let alpha: Vec<_> = alpha.f64()?.iter().collect();
+    let langs = column("lang", &DataType::String)?;
+    let langs: Vec<_> = langs.str()?.iter().collect();
 
     // DNB
     assert_eq!(paths[0], Some("0/dnb.txt"));
     assert_eq!(sizes[0], Some(769));
+    assert_eq!(
+        hashes[0],
+        Some(
+            "71eb6431db89ec3813c8a3b5bb9788fffefb291605c0b248ce5163285a8f6513"
+        )
+    );
+    assert_eq!(chars[0], Some(760));
+    assert_eq!(words[0], Some(98));
+    assert_eq!(lines[0], Some(5));
+    assert!((alpha[0].unwrap() - 0.8329).abs() < 1e-4);
+    assert_eq!(langs[0], Some("deu"));
 
     // TIB
     assert_eq!(paths[1], Some("0/tib.txt"));
     assert_eq!(sizes[1], Some(1443));
+    assert_eq!(
+        hashes[1],
+        Some(
+            "8f30b82a44c6af362798b5f69c63fdeb0aa78f9965159556b731e947e24efe0d"
+        )
+    );
+    assert_eq!(chars[1], Some(1422));
+    assert_eq!(words[1], Some(177));
+    assert_eq!(lines[1], Some(5));
+    assert!((alpha[1].unwrap() - 0.8383).abs() < 1e-4);
+    assert_eq!(langs[1], Some("deu"));
 
     // ZBW
     assert_eq!(paths[2], Some("1/zbw.txt"));
     assert_eq!(sizes[2], Some(908));
+    assert_eq!(
+        hashes[2],
+        Some(
+            "0bf81f967993abbf27e9fb2d0a02c8306d8c01bec51518c0373bdca7c4194abc"
+        )
+    );
+    assert_eq!(chars[2], Some(903));
+    assert_eq!(words[2], Some(128));
+    assert_eq!(lines[2], Some(1));
+    assert!((alpha[2].unwrap() - 0.8217).abs() < 1e-4);
+    assert_eq!(langs[2], Some("eng"));
 
     Ok(())
 }
@@ -149,3 +201,282 @@ fn index_num_threads_2() -> TestResult {
 
     Ok(())
 }
+
+#[test]
+fn index_kind() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let config = datashed_dir.join(Datashed::CONFIG);
+    let mut content = fs::read_to_string(&config)?;
+    content.push_str("\n[index.kind]\n");
+    content.push_str("article = [\"1/**\"]\n");
+    content.push_str("blurb = [\"0/dnb.txt\", \"0/zbw.txt\"]\n");
+    fs::write(&config, content)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-q"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    let df =
+        IpcReader::new(File::open(datashed_dir.join("index.ipc"))?)
+            .finish()?
+            .sort(["path"], SortMultipleOptions::default())?;
+
+    let kinds = df.column("kind")?.cast(&DataType::String)?;
+    let kinds: Vec<_> = kinds.str()?.iter().collect();
+    assert_eq!(kinds, vec![Some("blurb"), None, Some("article")]);
+
+    Ok(())
+}
+
+#[test]
+fn index_kind_incremental() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let config = datashed_dir.join(Datashed::CONFIG);
+    let mut content = fs::read_to_string(&config)?;
+    content.push_str("\n[index.kind]\n");
+    content.push_str("article = [\"1/**\"]\n");
+    fs::write(&config, content)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-v"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::ord::eq(
+            "Re-using 3 unchanged documents\n",
+        ));
+
+    let df =
+        IpcReader::new(File::open(datashed_dir.join("index.ipc"))?)
+            .finish()?
+            .sort(["path"], SortMultipleOptions::default())?;
+
+    let kinds = df.column("kind")?.cast(&DataType::String)?;
+    let kinds: Vec<_> = kinds.str()?.iter().collect();
+    assert_eq!(kinds, vec![None, None, Some("article")]);
+
+    Ok(())
+}
+
+#[test]
+fn index_kind_invalid_glob() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let config = datashed_dir.join(Datashed::CONFIG);
+    let mut content = fs::read_to_string(&config)?;
+    content.push_str("\n[index.kind]\narticle = [\"a/[\"]\n");
+    fs::write(&config, content)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-q"])
+        .assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::starts_with("error:"));
+
+    Ok(())
+}
+
+#[test]
+fn index_incremental() -> TestResult {
+    let datashed_dir = create_datashed()?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-v"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    let data_dir = datashed_dir.join(Datashed::DATA_DIR);
+    fs::write(data_dir.join("1/new.txt"), "foo")?;
+    fs::remove_file(data_dir.join("0/tib.txt"))?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-v"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::ord::eq(
+            "Re-using 2 unchanged documents\n",
+        ));
+
+    let df =
+        IpcReader::new(File::open(datashed_dir.join("index.ipc"))?)
+            .finish()?
+            .sort(["path"], SortMultipleOptions::default())?;
+
+    let paths: Vec<_> = df.column("path")?.str()?.iter().collect();
+    assert_eq!(
+        paths,
+        vec![Some("0/dnb.txt"), Some("1/new.txt"), Some("1/zbw.txt")]
+    );
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-v", "--full"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn index_include_exclude() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let data_dir = datashed_dir.join(Datashed::DATA_DIR);
+    fs::write(data_dir.join("1/README.md"), "# foo")?;
+    fs::write(data_dir.join("1/foo.xml"), "<foo/>")?;
+
+    let config = datashed_dir.join(Datashed::CONFIG);
+    let mut content = fs::read_to_string(&config)?;
+    content.push_str("\n[index]\n");
+    content.push_str("include = [\"**/*.txt\", \"**/*.md\"]\n");
+    content.push_str("exclude = [\"0/tib.txt\"]\n");
+    fs::write(&config, content)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-q"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    let df =
+        IpcReader::new(File::open(datashed_dir.join("index.ipc"))?)
+            .finish()?
+            .sort(["path"], SortMultipleOptions::default())?;
+
+    let paths: Vec<_> = df.column("path")?.str()?.iter().collect();
+    assert_eq!(
+        paths,
+        vec![Some("0/dnb.txt"), Some("1/README.md"), Some("1/zbw.txt")]
+    );
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-q", "--full"])
+        .args(["--include", "**/*.xml", "--include", "0/*"])
+        .args(["--exclude", "0/dnb.txt"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    let df =
+        IpcReader::new(File::open(datashed_dir.join("index.ipc"))?)
+            .finish()?
+            .sort(["path"], SortMultipleOptions::default())?;
+
+    let paths: Vec<_> = df.column("path")?.str()?.iter().collect();
+    assert_eq!(paths, vec![Some("0/tib.txt"), Some("1/foo.xml")]);
+
+    Ok(())
+}
+
+#[test]
+fn index_compressed() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let data_dir = datashed_dir.join(Datashed::DATA_DIR);
+    let content = fs::read(data_dir.join("0/dnb.txt"))?;
+
+    let mut encoder = GzEncoder::new(
+        File::create(data_dir.join("0/dnb.txt.gz"))?,
+        Compression::default(),
+    );
+    encoder.write_all(&content)?;
+    encoder.finish()?;
+
+    zstd::stream::copy_encode(
+        content.as_slice(),
+        File::create(data_dir.join("0/dnb.txt.zst"))?,
+        0,
+    )?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-q"])
+        .args(["--include", "0/dnb.*"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    let df =
+        IpcReader::new(File::open(datashed_dir.join("index.ipc"))?)
+            .finish()?
+            .sort(["path"], SortMultipleOptions::default())?;
+
+    let paths: Vec<_> = df.column("path")?.str()?.iter().collect();
+    assert_eq!(
+        paths,
+        vec![
+            Some("0/dnb.txt"),
+            Some("0/dnb.txt.gz"),
+            Some("0/dnb.txt.zst")
+        ]
+    );
+
+    let sizes = df.column("size")?.cast(&DataType::UInt64)?;
+    let sizes: Vec<_> = sizes.u64()?.iter().collect();
+    assert_eq!(sizes[0], Some(769));
+    assert!(sizes[1].unwrap() < 769);
+    assert!(sizes[2].unwrap() < 769);
+
+    let uncompressed_sizes =
+        df.column("uncompressed_size")?.cast(&DataType::UInt64)?;
+    assert!(uncompressed_sizes.u64()?.iter().all(|x| x == Some(769)));
+
+    let hashes = df.column("hash")?.str()?;
+    assert!(hashes.iter().all(|hash| hash == hashes.get(0)));
+
+    Ok(())
+}
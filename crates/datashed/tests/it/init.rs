@@ -389,3 +389,58 @@ fn init_quiet() -> TestResult {
 
     Ok(())
 }
+
+#[test]
+fn init_set_metadata() -> TestResult {
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let temp_dir = TempDir::new()?;
+
+    let assert = cmd
+        .current_dir(&temp_dir)
+        .arg("init")
+        .args(["--license", "CC0-1.0 OR CC-BY-4.0"])
+        .args(["--homepage", "https://example.org"])
+        .args(["--repository", "https://example.org/repo.git"])
+        .args(["-k", "corpus", "--keyword", "german"])
+        .args(["--provenance", "source=DNB"])
+        .arg("test-data")
+        .assert();
+
+    assert.success().code(0).stdout(predicates::str::is_empty());
+
+    let path = temp_dir.join("test-data").join(Datashed::CONFIG);
+    let config = Config::from_path(path)?;
+    let metadata = config.metadata;
+
+    assert_eq!(metadata.license, Some("CC0-1.0 OR CC-BY-4.0".into()));
+    assert_eq!(metadata.homepage, Some("https://example.org".into()));
+    assert_eq!(
+        metadata.repository,
+        Some("https://example.org/repo.git".into())
+    );
+    assert_eq!(metadata.keywords, vec!["corpus", "german"]);
+    assert_eq!(metadata.provenance.get("source").unwrap(), "DNB");
+    assert!(metadata.created.is_some());
+    assert!(metadata.updated.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn init_invalid_license() -> TestResult {
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let temp_dir = TempDir::new()?;
+
+    let assert = cmd
+        .current_dir(&temp_dir)
+        .arg("init")
+        .args(["--license", "Foo License"])
+        .arg("test-data")
+        .assert();
+
+    assert.failure().code(1).stderr(predicates::str::contains(
+        "invalid license expression 'Foo License'",
+    ));
+
+    Ok(())
+}
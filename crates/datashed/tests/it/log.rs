@@ -0,0 +1,94 @@
+use predicates::boolean::PredicateBooleanExt;
+
+use crate::prelude::*;
+
+#[test]
+fn log_version_bump() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["version", "--bump", "minor", "--author", "foo"])
+        .args(["-m", "Added documents"])
+        .assert();
+    assert.success().code(0);
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["version", "0.3.0", "--author", "bar"])
+        .assert();
+    assert.success().code(0);
+
+    assert!(datashed_dir.join(Datashed::CHANGELOG).is_file());
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd.current_dir(&datashed_dir).arg("log").assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::starts_with(
+            "version 0.3.0\nAuthor: bar\nDate:   ",
+        ))
+        .stdout(predicates::str::contains(
+            "\n\nversion 0.2.0\nAuthor: foo\nDate:   ",
+        ))
+        .stdout(predicates::str::contains(
+            "Index:  3 documents, 3120 bytes, ",
+        ))
+        .stdout(predicates::str::ends_with("\n\n    Added documents\n"))
+        .stderr(predicates::str::is_empty());
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["log", "-n", "1"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::contains("version 0.2.0").not());
+
+    Ok(())
+}
+
+#[test]
+fn log_empty() -> TestResult {
+    let datashed_dir = create_datashed()?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd.current_dir(&datashed_dir).arg("log").assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn log_message_requires_version() -> TestResult {
+    let datashed_dir = create_datashed()?;
+
+    for args in [["--message", "foo"], ["--author", "bar"]] {
+        let mut cmd = Command::cargo_bin("datashed")?;
+        let assert = cmd
+            .current_dir(&datashed_dir)
+            .arg("version")
+            .args(args)
+            .assert();
+
+        assert.failure().code(2).stderr(predicates::str::contains(
+            "the following required arguments were not provided",
+        ));
+    }
+
+    assert!(!datashed_dir.join(Datashed::CHANGELOG).exists());
+    Ok(())
+}
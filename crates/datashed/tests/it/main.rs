@@ -1,4 +1,17 @@
+mod archive;
+mod config;
+mod dedup;
+mod dehyphenate;
+mod export;
 mod index;
 mod init;
+mod log;
+mod normalize;
 mod prelude;
+mod select;
+mod split;
+mod stats;
+mod strip_boilerplate;
+mod subjects;
+mod verify;
 mod version;
@@ -0,0 +1,206 @@
+use std::fs;
+
+use crate::prelude::*;
+
+fn create_unnormalized_documents(datashed_dir: &TempDir) -> TestResult {
+    let data_dir = datashed_dir.join("data");
+    fs::write(
+        data_dir.join("0/nfd.txt"),
+        "Gru\u{0308}\u{00DF}e aus Mu\u{0308}nchen\r\n",
+    )?;
+    fs::write(
+        data_dir.join("1/controls.txt"),
+        "Hallo\u{200B} Welt\u{0007}\u{00AD}!  \nfoo\t  bar \n",
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn normalize_check() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_unnormalized_documents(&datashed_dir)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["normalize", "--check"])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicates::ord::eq("0/nfd.txt\n1/controls.txt\n"));
+
+    let content =
+        fs::read_to_string(datashed_dir.join("data/0/nfd.txt"))?;
+    assert_eq!(content, "Gru\u{0308}\u{00DF}e aus Mu\u{0308}nchen\r\n");
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args([
+            "normalize",
+            "--check",
+            "--filter",
+            "path == '0/nfd.txt'",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicates::ord::eq("0/nfd.txt\n"));
+
+    Ok(())
+}
+
+#[test]
+fn normalize_default() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_unnormalized_documents(&datashed_dir)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["normalize", "-q"])
+        .assert()
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty());
+
+    let data_dir = datashed_dir.join("data");
+    assert_eq!(
+        fs::read_to_string(data_dir.join("0/nfd.txt"))?,
+        "Grüße aus München\n"
+    );
+    assert_eq!(
+        fs::read_to_string(data_dir.join("1/controls.txt"))?,
+        "Hallo Welt!  \nfoo\t  bar \n"
+    );
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["normalize", "--check"])
+        .assert()
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn normalize_steps() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_unnormalized_documents(&datashed_dir)?;
+    create_index(&datashed_dir)?;
+
+    let path = datashed_dir.join(Datashed::CONFIG);
+    let mut config = Config::from_path(&path)?;
+    config.normalize.steps = vec!["whitespace".parse()?];
+    config.save()?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["normalize", "-q"])
+        .assert()
+        .success()
+        .code(0);
+
+    assert_eq!(
+        fs::read_to_string(datashed_dir.join("data/1/controls.txt"))?,
+        "Hallo\u{200B} Welt\u{0007}\u{00AD}!\nfoo bar\n"
+    );
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["normalize", "-q", "--step", "line-endings,nfkc"])
+        .assert()
+        .success()
+        .code(0);
+
+    assert_eq!(
+        fs::read_to_string(datashed_dir.join("data/0/nfd.txt"))?,
+        "Grüße aus München\n"
+    );
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["normalize", "--step", "foo"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains(
+            "invalid normalization step",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn normalize_historic_german() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    fs::write(
+        datashed_dir.join("data/0/fraktur.txt"),
+        "Daß ſie die Muͤhe nicht ſcheuen, iſt ein Theil\n\
+         der Erkenntniß: ZWEY Buͤcher ſind beſſer als eins, \
+         wenn es ſeyn muß.\n",
+    )?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["normalize", "-q", "--step", "historic-german"])
+        .assert()
+        .success()
+        .code(0);
+
+    assert_eq!(
+        fs::read_to_string(datashed_dir.join("data/0/fraktur.txt"))?,
+        "Dass sie die Mühe nicht scheuen, ist ein Teil\n\
+         der Erkenntnis: ZWEI Bücher sind besser als eins, \
+         wenn es sein muss.\n",
+    );
+
+    assert_eq!(
+        fs::read_to_string(datashed_dir.join("data/0/dnb.txt"))?,
+        fs::read_to_string(data_dir().join("dnb.txt"))?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn normalize_skip_non_utf8() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let data_dir = datashed_dir.join("data");
+    fs::write(data_dir.join("0/latin1.txt"), b"Gr\xfc\xdfe\r\n")?;
+    fs::write(data_dir.join("1/bom.txt"), b"\xef\xbb\xbfHallo\r\n")?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["normalize", "--check"])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicates::ord::eq("1/bom.txt\n"))
+        .stderr(predicates::ord::eq(
+            "warning: skipped 0/latin1.txt (not valid UTF-8)\n",
+        ));
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["normalize", "-q"])
+        .assert()
+        .success()
+        .code(0)
+        .stderr(predicates::str::is_empty());
+
+    assert_eq!(
+        fs::read(data_dir.join("0/latin1.txt"))?,
+        b"Gr\xfc\xdfe\r\n"
+    );
+    assert_eq!(
+        fs::read_to_string(data_dir.join("1/bom.txt"))?,
+        "Hallo\n"
+    );
+
+    Ok(())
+}
@@ -60,3 +60,17 @@ pub(crate) fn create_datashed() -> anyhow::Result<TempDir> {
 
     Ok(temp_dir)
 }
+
+pub(crate) fn create_index(datashed_dir: &TempDir) -> TestResult {
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert =
+        cmd.current_dir(datashed_dir).args(["index", "-q"]).assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use std::fs::{self, File};
+
+use polars::prelude::*;
+use predicates::boolean::PredicateBooleanExt;
+
+use crate::prelude::*;
+
+#[test]
+fn select_default() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["select", "size > 800 && lang == 'deu'"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::starts_with("path,size,"))
+        .stdout(predicates::str::contains("0/tib.txt,1443,"))
+        .stdout(predicates::str::contains("dnb.txt").not())
+        .stdout(predicates::str::contains("zbw.txt").not())
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn select_output() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let output = datashed_dir.join("selection.ipc");
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .arg("select")
+        .args(["-o", output.to_str().unwrap()])
+        .arg("!(path == \"0/tib.txt\") || words < 100")
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    let df = IpcReader::new(File::open(output)?)
+        .finish()?
+        .sort(["path"], SortMultipleOptions::default())?;
+
+    let paths: Vec<_> = df.column("path")?.str()?.iter().collect();
+    assert_eq!(paths, vec![Some("0/dnb.txt"), Some("1/zbw.txt")]);
+
+    Ok(())
+}
+
+#[test]
+fn select_kind() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let config = datashed_dir.join(Datashed::CONFIG);
+    let mut content = fs::read_to_string(&config)?;
+    content.push_str("\n[index.kind]\n");
+    content.push_str("article = [\"1/**\"]\n");
+    content.push_str("blurb = [\"0/dnb.txt\"]\n");
+    fs::write(&config, content)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["select", "kind == \"article\""])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::contains("1/zbw.txt,"))
+        .stdout(predicates::str::contains("0/").not())
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn select_invalid_filter() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["select", "size > "])
+        .assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::ord::eq(
+            "error: unexpected end of filter expression\n",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use std::fs;
+
+use crate::prelude::*;
+
+fn create_corpus() -> anyhow::Result<TempDir> {
+    let datashed_dir = create_datashed()?;
+    let data_dir = datashed_dir.join(Datashed::DATA_DIR);
+    for i in 0..17 {
+        fs::write(data_dir.join(format!("1/{i}.txt")), format!("{i}"))?;
+    }
+
+    create_index(&datashed_dir)?;
+    Ok(datashed_dir)
+}
+
+#[test]
+fn split_default() -> TestResult {
+    let datashed_dir = create_corpus()?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["split", "-v"])
+        .assert();
+
+    let assert = assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::starts_with("path,split\n"))
+        .stderr(predicates::ord::eq(
+            "train: 16 documents\ntest: 4 documents\n",
+        ));
+
+    let output = assert.get_output().stdout.clone();
+    assert_eq!(String::from_utf8(output.clone())?.lines().count(), 21);
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd.current_dir(&datashed_dir).arg("split").assert();
+    assert.success().code(0).stdout(predicates::ord::eq(output));
+
+    Ok(())
+}
+
+#[test]
+fn split_seed() -> TestResult {
+    let datashed_dir = create_corpus()?;
+
+    let mut outputs = vec![];
+    for seed in ["1", "1", "2"] {
+        let mut cmd = Command::cargo_bin("datashed")?;
+        let assert = cmd
+            .current_dir(&datashed_dir)
+            .args(["split", "--seed", seed, "-r", "0.5,0.25,0.25"])
+            .assert();
+
+        let output = assert.success().code(0).get_output().clone();
+        let stdout = String::from_utf8(output.stdout)?;
+        assert_eq!(stdout.matches(",train\n").count(), 10);
+        assert_eq!(stdout.matches(",test\n").count(), 5);
+        assert_eq!(stdout.matches(",validation\n").count(), 5);
+        outputs.push(stdout);
+    }
+
+    assert_eq!(outputs[0], outputs[1]);
+    assert_ne!(outputs[0], outputs[2]);
+
+    Ok(())
+}
+
+#[test]
+fn split_stratify() -> TestResult {
+    let datashed_dir = create_corpus()?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["split", "--stratify", "lang", "-r", "0.5,0.5"])
+        .assert();
+
+    let output = assert.success().code(0).get_output().clone();
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.starts_with("path,lang,split\n"));
+
+    // Both german documents must be assigned to different sets.
+    let german: Vec<_> = stdout
+        .lines()
+        .filter(|line| line.contains(",deu,"))
+        .collect();
+    assert_eq!(german.len(), 2);
+    assert_ne!(
+        german[0].rsplit(',').next(),
+        german[1].rsplit(',').next()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn split_invalid_ratios() -> TestResult {
+    let datashed_dir = create_corpus()?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["split", "-r", "0.5,0.6"])
+        .assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::ord::eq(
+            "error: ratios must sum up to 1\n",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use predicates::boolean::PredicateBooleanExt;
+
+use crate::prelude::*;
+
+#[test]
+fn stats_table() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd.current_dir(&datashed_dir).arg("stats").assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::starts_with(
+            "version        0.1.0\n\
+             documents      3\n\
+             size (total)   3120\n\
+             size (mean)    1040.00\n\
+             size (min)     769\n\
+             size (p25)     769\n\
+             size (median)  908\n\
+             size (p75)     1443\n\
+             size (p90)     1443\n\
+             size (p99)     1443\n\
+             size (max)     1443\n\
+             \n\
+             directory  documents\n\
+             0                  2\n\
+             1                  1\n",
+        ))
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn stats_json() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["stats", "--json"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::contains("\"version\": \"0.1.0\""))
+        .stdout(predicates::str::contains("\"documents\": 3"))
+        .stdout(predicates::str::contains("\"total\": 3120"))
+        .stdout(predicates::str::contains("\"median\": 908"))
+        .stdout(predicates::str::contains("\"kind\"").not())
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn stats_missing_index() -> TestResult {
+    let datashed_dir = create_datashed()?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd.current_dir(&datashed_dir).arg("stats").assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stderr(predicates::str::contains("index not found"));
+
+    Ok(())
+}
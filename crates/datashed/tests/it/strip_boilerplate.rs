@@ -0,0 +1,215 @@
+use std::fs;
+
+use predicates::boolean::PredicateBooleanExt;
+
+use crate::prelude::*;
+
+fn create_paged_documents(datashed_dir: &TempDir) -> TestResult {
+    let data_dir = datashed_dir.join("data/2");
+    fs::create_dir(&data_dir)?;
+
+    for (name, text) in
+        [("a", "Erster"), ("b", "Zweiter"), ("c", "Dritter")]
+    {
+        let mut pages = vec![];
+        for (page, ordinal) in
+            ["ersten", "zweiten", "dritten"].iter().enumerate()
+        {
+            pages.push(format!(
+                "Muster Verlag\n\n{text} Text der {ordinal} Seite.\n\n\
+                 - {} -\n",
+                page + 1
+            ));
+        }
+
+        let mut content = pages.join("\u{000C}");
+
+        if name != "c" {
+            content.push_str("Alle Rechte vorbehalten.\n");
+        }
+
+        fs::write(data_dir.join(format!("{name}.txt")), content)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn strip_boilerplate() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_paged_documents(&datashed_dir)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["strip-boilerplate", "-q", "--min-count", "2"])
+        .assert()
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq(
+            "path,line,count\n\
+             2/a.txt,Muster Verlag,3\n\
+             2/a.txt,- 1 -,1\n\
+             2/a.txt,- 2 -,1\n\
+             2/a.txt,- 3 -,1\n\
+             2/a.txt,Alle Rechte vorbehalten.,1\n\
+             2/b.txt,Muster Verlag,3\n\
+             2/b.txt,- 1 -,1\n\
+             2/b.txt,- 2 -,1\n\
+             2/b.txt,- 3 -,1\n\
+             2/b.txt,Alle Rechte vorbehalten.,1\n\
+             2/c.txt,Muster Verlag,3\n\
+             2/c.txt,- 1 -,1\n\
+             2/c.txt,- 2 -,1\n\
+             2/c.txt,- 3 -,1\n",
+        ))
+        .stderr(predicates::str::is_empty());
+
+    assert_eq!(
+        fs::read_to_string(datashed_dir.join("data/2/c.txt"))?,
+        "\nDritter Text der ersten Seite.\n\n\u{000C}\
+         \nDritter Text der zweiten Seite.\n\n\u{000C}\
+         \nDritter Text der dritten Seite.\n\n"
+    );
+
+    assert_eq!(
+        fs::read_to_string(datashed_dir.join("data/0/dnb.txt"))?,
+        fs::read_to_string(data_dir().join("dnb.txt"))?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn strip_boilerplate_dry_run() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_paged_documents(&datashed_dir)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["strip-boilerplate", "-q", "-n", "-r", "report.csv"])
+        .args(["--min-count", "3"])
+        .args(["--filter", "path == '2/a.txt'"])
+        .assert()
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    assert_eq!(
+        fs::read_to_string(datashed_dir.join("report.csv"))?,
+        "path,line,count\n\
+         2/a.txt,Muster Verlag,3\n\
+         2/a.txt,- 1 -,1\n\
+         2/a.txt,- 2 -,1\n\
+         2/a.txt,- 3 -,1\n"
+    );
+
+    let content =
+        fs::read_to_string(datashed_dir.join("data/2/a.txt"))?;
+    assert!(content.starts_with("Muster Verlag\n"));
+
+    Ok(())
+}
+
+#[test]
+fn strip_boilerplate_invalid_share() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["strip-boilerplate", "--min-share", "1.5"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicates::str::contains(
+            "min-share must be between 0 and 1",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn strip_boilerplate_skip_non_utf8() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let content = b"Kopfzeile\nGr\xfc\xdfe\nKopfzeile\nKopfzeile\n";
+    let path = datashed_dir.join("data/0/latin1.txt");
+    fs::write(&path, content)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["strip-boilerplate"])
+        .assert()
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq("path,line,count\n"))
+        .stderr(predicates::str::contains(
+            "warning: skipped 0/latin1.txt (not valid UTF-8)",
+        ));
+
+    assert_eq!(fs::read(&path)?, content);
+    Ok(())
+}
+
+#[test]
+fn strip_boilerplate_keep_headings() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let words = [
+        "erste", "zweite", "dritte", "vierte", "fünfte", "sechste",
+        "siebte", "achte",
+    ];
+
+    // Chapters start on the first, fourth and seventh page.
+    let mut pages = vec![];
+    let mut expected = vec![];
+    for (idx, word) in words.iter().enumerate() {
+        let heading = match idx % 3 {
+            0 => format!("Kapitel {}\n", idx / 3 + 1),
+            _ => String::new(),
+        };
+
+        let body = format!(
+            "{heading}\nDer {word} Absatz.\n\n§ {}\n\n\
+             Die {word} Seite endet hier.\n",
+            idx + 1
+        );
+
+        pages.push(format!("Muster Verlag\n{body}\n- {} -\n", idx + 1));
+        expected.push(format!("{body}\n"));
+    }
+
+    let data_dir = datashed_dir.join("data");
+    let content = pages.join("\u{000C}");
+    fs::write(data_dir.join("0/paged.txt"), &content)?;
+
+    let content = "Kapitel 1\n\nText eins.\n\nKapitel 2\n\nText zwei.\n\n\
+                   Kapitel 3\n\nText drei.\n";
+    fs::write(data_dir.join("1/chapters.txt"), content)?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    cmd.current_dir(&datashed_dir)
+        .args(["strip-boilerplate", "-q"])
+        .assert()
+        .success()
+        .code(0)
+        .stdout(predicates::str::contains(
+            "0/paged.txt,Muster Verlag,8",
+        ))
+        .stdout(predicates::str::contains("Kapitel").not())
+        .stdout(predicates::str::contains("§").not());
+
+    assert_eq!(
+        fs::read_to_string(data_dir.join("0/paged.txt"))?,
+        expected.join("\u{000C}")
+    );
+    assert_eq!(
+        fs::read_to_string(data_dir.join("1/chapters.txt"))?,
+        content
+    );
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use std::fs;
+
+use crate::prelude::*;
+
+fn import_subjects(
+    datashed_dir: &TempDir,
+    args: &[&str],
+) -> TestResult {
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(datashed_dir)
+        .args(["subjects", "import"])
+        .args(args)
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn subjects_import_csv() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    fs::write(
+        datashed_dir.join("subjects.csv"),
+        "id,uri\n\
+         0/dnb.txt,<http://d-nb.info/gnd/2>\n\
+         0/dnb.txt,http://d-nb.info/gnd/1\n\
+         0/dnb.txt,http://d-nb.info/gnd/1\n\
+         2/foo.txt,http://d-nb.info/gnd/3\n",
+    )?;
+
+    import_subjects(
+        &datashed_dir,
+        &["--key", "id", "--subject", "uri", "subjects.csv"],
+    )?;
+
+    assert!(datashed_dir.join(Datashed::SUBJECTS).is_file());
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["subjects", "stats", "--top", "1"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq(
+            "documents                     2\n\
+             assignments                   3\n\
+             subjects                      3\n\
+             subjects per document (mean)  1.50\n\
+             subjects per document (min)   1\n\
+             subjects per document (max)   2\n\
+             documents without subjects    2\n\
+             unknown documents             1\n\
+             \n\
+             top subjects:\n       \
+             1  http://d-nb.info/gnd/1\n",
+        ))
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn subjects_import_jsonl_append() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    fs::write(
+        datashed_dir.join("subjects.csv"),
+        "path,subject\n0/dnb.txt,http://d-nb.info/gnd/1\n",
+    )?;
+
+    fs::write(
+        datashed_dir.join("subjects.jsonl"),
+        "{\"path\":\"1/zbw.txt\",\"subject\":[\"http://d-nb.info/gnd/2\",\
+         \"http://d-nb.info/gnd/3\"]}\n\
+         {\"path\":\"0/dnb.txt\",\"subject\":[\"http://d-nb.info/gnd/1\"]}\n",
+    )?;
+
+    import_subjects(&datashed_dir, &["subjects.csv"])?;
+    import_subjects(&datashed_dir, &["--append", "subjects.jsonl"])?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["export", "--format", "annif-tsv", "--max-length", "3"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::ord::eq(
+            "Die\t<http://d-nb.info/gnd/1>\n\
+             The\t<http://d-nb.info/gnd/2> <http://d-nb.info/gnd/3>\n",
+        ))
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn subjects_stats_missing() -> TestResult {
+    let datashed_dir = create_datashed()?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["subjects", "stats"])
+        .assert();
+
+    assert.failure().code(1).stderr(predicates::str::contains(
+        "subjects not found (run `datashed subjects import` first)",
+    ));
+
+    Ok(())
+}
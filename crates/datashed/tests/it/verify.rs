@@ -0,0 +1,130 @@
+use std::fs;
+
+use crate::prelude::*;
+
+#[test]
+fn verify_default() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["verify", "-q"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn verify_discrepancies() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    create_index(&datashed_dir)?;
+
+    let data_dir = datashed_dir.join(Datashed::DATA_DIR);
+    fs::remove_file(data_dir.join("0/tib.txt"))?;
+    fs::write(data_dir.join("1/new.txt"), "foo")?;
+    fs::write(data_dir.join("1/zbw.txt"), "bar")?;
+
+    let mut content = fs::read(data_dir.join("0/dnb.txt"))?;
+    content[0] = b'X';
+    fs::write(data_dir.join("0/dnb.txt"), content)?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["verify", "-q"])
+        .assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stdout(predicates::ord::eq(
+            "0/dnb.txt: hash mismatch\n\
+             0/tib.txt: missing\n\
+             1/new.txt: new\n\
+             1/zbw.txt: size mismatch (expected 908, found 3)\n",
+        ))
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn verify_missing_index() -> TestResult {
+    let datashed_dir = create_datashed()?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["verify", "-q"])
+        .assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::ord::eq(
+            "error: index not found (run `datashed index` first)\n",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn verify_index_include() -> TestResult {
+    let datashed_dir = create_datashed()?;
+    let data_dir = datashed_dir.join(Datashed::DATA_DIR);
+    fs::write(data_dir.join("1/README.md"), "# foo")?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-q", "--include", "**/*.md"])
+        .assert();
+    assert.success().code(0);
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["verify", "-q"])
+        .assert();
+
+    assert
+        .failure()
+        .code(1)
+        .stdout(predicates::ord::eq(
+            "0/dnb.txt: new\n0/tib.txt: new\n1/zbw.txt: new\n",
+        ))
+        .stderr(predicates::str::is_empty());
+
+    fs::remove_file(data_dir.join("0/tib.txt"))?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["index", "-q", "--include", "**/*.md"])
+        .args(["--include", "**/*.txt"])
+        .assert();
+    assert.success().code(0);
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&datashed_dir)
+        .args(["verify", "-q"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use std::fs;
+
 use crate::prelude::*;
 
 #[test]
@@ -170,3 +172,44 @@ fn version_bump_patch() -> TestResult {
 
     Ok(())
 }
+
+#[test]
+fn version_bump_preserve_formatting() -> TestResult {
+    let temp_dir = create_datashed()?;
+    let path = temp_dir.join(Datashed::CONFIG);
+
+    fs::write(
+        &path,
+        "# datashed config\n\
+         [metadata]\n\
+         name = 'foo'  # the name\n\
+         version = \"0.1.0\" # the version\n\
+         \n\
+         [custom]\n\
+         owner = \"bar\"\n",
+    )?;
+
+    let mut cmd = Command::cargo_bin("datashed")?;
+    let assert = cmd
+        .current_dir(&temp_dir)
+        .args(["version", "--bump", "minor"])
+        .assert();
+
+    assert
+        .success()
+        .code(0)
+        .stdout(predicates::str::is_empty())
+        .stderr(predicates::str::is_empty());
+
+    let content = fs::read_to_string(&path)?;
+    assert!(content.starts_with(
+        "# datashed config\n\
+         [metadata]\n\
+         name = 'foo'  # the name\n\
+         version = \"0.2.0\" # the version\n\
+         updated = "
+    ));
+    assert!(content.ends_with("\n\n[custom]\nowner = \"bar\"\n"));
+
+    Ok(())
+}